@@ -1,7 +1,419 @@
 use std::env;
-use std::fs::File;
+use std::fmt::Write as _;
+use std::fs::{self, File};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use toml::{Table, Value};
+
+/// Number of user GPIOs on the RP2350A.
+const GPIO_COUNT: u64 = 30;
+
+/// Largest debounce count accepted by the firmware.
+const MAX_DEBOUNCE_COUNT: u64 = 100;
+
+/// `SEQUENCE_TIMEOUT_MS` in `src/config.rs`.
+const SEQUENCE_TIMEOUT_MS: u64 = 3000;
+
+/// `BLINK_DELAY_MS` in `src/config.rs`.
+const BLINK_DELAY_MS: u64 = 500;
+
+/// `LATENCY_BUCKET_US * LATENCY_BUCKETS` in `src/config.rs`.
+const LATENCY_RANGE_US: u64 = 5000 * 16;
+
+/// Board wiring from the `[board]` section.
+struct Board {
+    name: String,
+    button_pin: u64,
+    button_polarity: &'static str,
+    button_pull: &'static str,
+    schmitt: bool,
+    bypass_sync: bool,
+    led_pin: u64,
+    led_polarity: &'static str,
+}
+
+/// Parsed and validated `button.toml`.
+///
+/// Absent values are `None` and fall back to the defaults in
+/// `src/config.rs`.
+#[derive(Default)]
+struct ButtonConfig {
+    board: Option<Board>,
+    debounce_delay_ms: Option<u64>,
+    debounce_count: Option<u64>,
+    long_press_ms: Option<u64>,
+    debounce_strategy: Option<&'static str>,
+    led_tick_ms: Option<u64>,
+    fault_blink_ms: Option<u64>,
+    fault_pause_ms: Option<u64>,
+    morse_wpm: Option<u64>,
+    keymap: Option<Vec<Vec<char>>>,
+}
+
+/// One table of `button.toml` with its name for error messages.
+struct Section<'a> {
+    name: &'static str,
+    table: &'a Table,
+}
+
+/// Stops the build with a configuration error.
+fn fail(message: impl AsRef<str>) -> ! {
+    eprintln!("error: button.toml: {}", message.as_ref());
+    process::exit(1);
+}
+
+impl<'a> Section<'a> {
+    /// Returns the named table, rejecting keys it does not know.
+    fn get(root: &'a Table, name: &'static str, keys: &[&str]) -> Option<Self> {
+        let table = match root.get(name)? {
+            Value::Table(table) => table,
+            _ => fail(format!("[{name}] must be a table")),
+        };
+        if let Some(key) = table.keys().find(|key| !keys.contains(&key.as_str())) {
+            fail(format!(
+                "unknown key `{key}` in [{name}], expected one of {keys:?}"
+            ));
+        }
+        Some(Self { name, table })
+    }
+
+    /// Returns an integer in `min..=max`.
+    fn int(&self, key: &str, min: u64, max: u64) -> Option<u64> {
+        let value = self.table.get(key)?;
+        let Some(value) = value.as_integer() else {
+            fail(format!("{}.{key} must be an integer", self.name));
+        };
+        if value < min as i64 || value > max as i64 {
+            fail(format!(
+                "{}.{key} = {value} is out of range {min}..={max}",
+                self.name
+            ));
+        }
+        Some(value as u64)
+    }
+
+    /// Returns a string.
+    fn string(&self, key: &str) -> Option<&'a str> {
+        match self.table.get(key)? {
+            Value::String(value) => Some(value),
+            _ => fail(format!("{}.{key} must be a string", self.name)),
+        }
+    }
+
+    /// Returns a boolean.
+    fn bool(&self, key: &str) -> Option<bool> {
+        match self.table.get(key)? {
+            Value::Boolean(value) => Some(*value),
+            _ => fail(format!("{}.{key} must be true or false", self.name)),
+        }
+    }
+
+    /// Returns one of `choices`, mapped to its Rust variant name.
+    fn choice(&self, key: &str, choices: &[(&str, &'static str)]) -> Option<&'static str> {
+        let value = self.string(key)?;
+        match choices.iter().find(|(name, _)| *name == value) {
+            Some((_, variant)) => Some(variant),
+            None => {
+                let names: Vec<_> = choices.iter().map(|(name, _)| *name).collect();
+                fail(format!(
+                    "{}.{key} = {value:?}, expected one of {names:?}",
+                    self.name
+                ))
+            }
+        }
+    }
+}
+
+impl ButtonConfig {
+    /// Parses and validates the configuration.
+    fn parse(root: &Table) -> Self {
+        const SECTIONS: [&str; 4] = ["board", "debounce", "led", "keypad"];
+        if let Some(name) = root.keys().find(|name| !SECTIONS.contains(&name.as_str())) {
+            fail(format!(
+                "unknown section [{name}], expected one of {SECTIONS:?}"
+            ));
+        }
+        let mut config = Self::default();
+        if let Some(board) = Section::get(
+            root,
+            "board",
+            &[
+                "name",
+                "button_pin",
+                "button_polarity",
+                "button_pull",
+                "schmitt",
+                "bypass_sync",
+                "led_pin",
+                "led_polarity",
+            ],
+        ) {
+            config.board = Some(Self::parse_board(&board));
+        }
+        if let Some(debounce) = Section::get(
+            root,
+            "debounce",
+            &["delay_ms", "count", "long_press_ms", "strategy"],
+        ) {
+            config.debounce_delay_ms = debounce.int("delay_ms", 1, 1000);
+            config.debounce_count = debounce.int("count", 1, MAX_DEBOUNCE_COUNT);
+            config.long_press_ms = debounce.int("long_press_ms", 1, 60_000);
+            config.debounce_strategy = debounce.choice(
+                "strategy",
+                &[("conservative", "Conservative"), ("eager", "Eager")],
+            );
+        }
+        if let Some(led) = Section::get(
+            root,
+            "led",
+            &["tick_ms", "fault_blink_ms", "fault_pause_ms", "morse_wpm"],
+        ) {
+            config.led_tick_ms = led.int("tick_ms", 1, 1000);
+            config.fault_blink_ms = led.int("fault_blink_ms", 1, 10_000);
+            config.fault_pause_ms = led.int("fault_pause_ms", 1, 10_000);
+            config.morse_wpm = led.int("morse_wpm", 5, 60);
+        }
+        if let Some(keypad) = Section::get(root, "keypad", &["keymap"]) {
+            config.keymap = Some(Self::parse_keymap(&keypad));
+        }
+        config.check_timing();
+        config
+    }
+
+    /// Parses the `[board]` section.
+    fn parse_board(section: &Section) -> Board {
+        const POLARITY: [(&str, &str); 2] =
+            [("active-low", "ActiveLow"), ("active-high", "ActiveHigh")];
+        const PULL: [(&str, &str); 3] = [("up", "Up"), ("down", "Down"), ("none", "None")];
+        let Some(button_pin) = section.int("button_pin", 0, GPIO_COUNT - 1) else {
+            fail("board.button_pin is required");
+        };
+        let Some(led_pin) = section.int("led_pin", 0, GPIO_COUNT - 1) else {
+            fail("board.led_pin is required");
+        };
+        if button_pin == led_pin {
+            fail(format!(
+                "board.button_pin and board.led_pin are both GPIO{button_pin}"
+            ));
+        }
+        let button_polarity = section
+            .choice("button_polarity", &POLARITY)
+            .unwrap_or("ActiveLow");
+        let default_pull = if button_polarity == "ActiveLow" {
+            "Up"
+        } else {
+            "Down"
+        };
+        let button_pull = section.choice("button_pull", &PULL).unwrap_or(default_pull);
+        if matches!(
+            (button_polarity, button_pull),
+            ("ActiveLow", "Down") | ("ActiveHigh", "Up")
+        ) {
+            fail("board.button_pull holds the button pressed; use the opposite pull or \"none\"");
+        }
+        let schmitt = section.bool("schmitt").unwrap_or(true);
+        let bypass_sync = section.bool("bypass_sync").unwrap_or(false);
+        if bypass_sync && !schmitt {
+            fail("board.bypass_sync requires board.schmitt = true");
+        }
+        Board {
+            name: section.string("name").unwrap_or("button.toml").to_string(),
+            button_pin,
+            button_polarity,
+            button_pull,
+            schmitt,
+            bypass_sync,
+            led_pin,
+            led_polarity: section
+                .choice("led_polarity", &POLARITY)
+                .unwrap_or("ActiveHigh"),
+        }
+    }
+
+    /// Parses `[keypad] keymap`, one string per row.
+    fn parse_keymap(section: &Section) -> Vec<Vec<char>> {
+        let Some(Value::Array(rows)) = section.table.get("keymap") else {
+            fail("keypad.keymap must be an array of strings, one per row");
+        };
+        let keymap: Vec<Vec<char>> = rows
+            .iter()
+            .map(|row| match row {
+                Value::String(row) => row.chars().collect(),
+                _ => fail("keypad.keymap rows must be strings"),
+            })
+            .collect();
+        let cols = keymap.first().map_or(0, Vec::len);
+        if keymap.is_empty() || keymap.len() > 16 {
+            fail(format!(
+                "keypad.keymap has {} rows, expected 1..=16",
+                keymap.len()
+            ));
+        }
+        if cols == 0 || cols > 32 {
+            fail(format!("keypad.keymap has {cols} columns, expected 1..=32"));
+        }
+        if let Some(row) = keymap.iter().position(|row| row.len() != cols) {
+            fail(format!("keypad.keymap row {row} does not have {cols} keys"));
+        }
+        keymap
+    }
+
+    /// Checks values that depend on each other.
+    fn check_timing(&self) {
+        let delay = self.debounce_delay_ms.unwrap_or(5);
+        let count = self.debounce_count.unwrap_or(5);
+        let long_press = self.long_press_ms.unwrap_or(1000);
+        let wpm = self.morse_wpm.unwrap_or(12);
+        if long_press <= delay * count {
+            fail(format!(
+                "debounce.long_press_ms = {long_press} must exceed the debounce window of {} ms",
+                delay * count
+            ));
+        }
+        if long_press >= SEQUENCE_TIMEOUT_MS {
+            fail(format!(
+                "debounce.long_press_ms = {long_press} must stay below the \
+                 {SEQUENCE_TIMEOUT_MS} ms unlock sequence timeout"
+            ));
+        }
+        if delay >= BLINK_DELAY_MS {
+            fail(format!(
+                "debounce.delay_ms = {delay} must stay below the {BLINK_DELAY_MS} ms blink delay"
+            ));
+        }
+        // A press is confirmed up to one sample after the stable window.
+        let window = delay * (count + 1);
+        if window * 1000 >= LATENCY_RANGE_US {
+            fail(format!(
+                "debounce window of {window} ms must fit the {} ms latency histogram; \
+                 lower debounce.delay_ms or debounce.count",
+                LATENCY_RANGE_US / 1000
+            ));
+        }
+        if 1200 / wpm <= window {
+            fail(format!(
+                "led.morse_wpm = {wpm} gives a {} ms dit, which must exceed the \
+                 debounce window of {window} ms",
+                1200 / wpm
+            ));
+        }
+        let tick = self.led_tick_ms.unwrap_or(10);
+        let blink = self.fault_blink_ms.unwrap_or(100);
+        let pause = self.fault_pause_ms.unwrap_or(600);
+        if blink <= tick {
+            fail(format!(
+                "led.fault_blink_ms = {blink} must exceed led.tick_ms = {tick}"
+            ));
+        }
+        if pause < tick {
+            fail(format!(
+                "led.fault_pause_ms = {pause} must be at least led.tick_ms = {tick}"
+            ));
+        }
+    }
+
+    /// Renders the constants included by `src/config.rs`.
+    fn constants(&self) -> String {
+        let mut out = String::from("// Generated by build.rs from button.toml. Do not edit.\n\n");
+        for (name, ty, value) in [
+            ("DEBOUNCE_DELAY_MS", "u64", self.debounce_delay_ms),
+            ("DEBOUNCE_COUNT", "u32", self.debounce_count),
+            ("LONG_PRESS_MS", "u64", self.long_press_ms),
+            ("LED_TICK_MS", "u64", self.led_tick_ms),
+            ("FAULT_BLINK_MS", "u32", self.fault_blink_ms),
+            ("FAULT_PAUSE_MS", "u32", self.fault_pause_ms),
+            ("MORSE_WPM", "u32", self.morse_wpm),
+        ] {
+            let value = value.map_or("None".to_string(), |value| format!("Some({value})"));
+            writeln!(out, "pub const {name}: Option<{ty}> = {value};").unwrap();
+        }
+        let strategy = self
+            .debounce_strategy
+            .map_or("None".to_string(), |strategy| {
+                format!("Some(crate::button::DebounceStrategy::{strategy})")
+            });
+        writeln!(
+            out,
+            "pub const DEBOUNCE_STRATEGY: Option<crate::button::DebounceStrategy> = {strategy};"
+        )
+        .unwrap();
+        match &self.board {
+            Some(board) => writeln!(
+                out,
+                "pub const BOARD: Option<crate::board::BoardProfile> = \
+                 Some(crate::board::BoardProfile {{ name: {:?}, button_pin: {}, \
+                 button_polarity: crate::board::Polarity::{}, button_pull: crate::board::Pull::{}, \
+                 button_schmitt: {}, button_bypass_sync: {}, \
+                 led_pin: {}, led_polarity: crate::board::Polarity::{} }});",
+                board.name,
+                board.button_pin,
+                board.button_polarity,
+                board.button_pull,
+                board.schmitt,
+                board.bypass_sync,
+                board.led_pin,
+                board.led_polarity
+            ),
+            None => writeln!(
+                out,
+                "pub const BOARD: Option<crate::board::BoardProfile> = None;"
+            ),
+        }
+        .unwrap();
+        match &self.keymap {
+            Some(keymap) => {
+                let rows: Vec<String> = keymap.iter().map(|row| format!("{row:?}")).collect();
+                writeln!(out, "pub const KEYMAP_ROWS: usize = {};", keymap.len()).unwrap();
+                writeln!(out, "pub const KEYMAP_COLS: usize = {};", keymap[0].len()).unwrap();
+                writeln!(
+                    out,
+                    "pub const KEYMAP: [[char; KEYMAP_COLS]; KEYMAP_ROWS] = [{}];",
+                    rows.join(", ")
+                )
+                .unwrap();
+            }
+            None => out.push_str(
+                "pub const KEYMAP_ROWS: usize = 4;\npub const KEYMAP_COLS: usize = 4;\n\
+                 pub const KEYMAP: [[char; KEYMAP_COLS]; KEYMAP_ROWS] = crate::keypad::KEYMAP_4X4;\n",
+            ),
+        }
+        out
+    }
+
+    /// Renders the pin macro included by `src/main.rs` for `[board]`.
+    fn board_pins(&self) -> String {
+        let mut out = String::from("// Generated by build.rs from button.toml. Do not edit.\n\n");
+        if let Some(board) = &self.board {
+            write!(
+                out,
+                "macro_rules! file_board_pins {{\n    ($p:ident) => {{\n        \
+                 (board_pin!($p.PIN_{}, BUTTON_PIN), board_pin!($p.PIN_{}, LED_PIN))\n    }};\n}}\n",
+                board.button_pin, board.led_pin
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+/// Loads `button.toml`, or the file named by `BUTTON_CONFIG`.
+fn load(manifest_dir: &Path) -> Table {
+    let explicit = env::var_os("BUTTON_CONFIG").map(PathBuf::from);
+    let path = manifest_dir.join(explicit.as_deref().unwrap_or(Path::new("button.toml")));
+    println!("cargo:rerun-if-changed={}", path.display());
+    if !path.exists() {
+        if explicit.is_some() {
+            fail(format!("BUTTON_CONFIG file {} not found", path.display()));
+        }
+        return Table::new();
+    }
+    let text = fs::read_to_string(&path)
+        .unwrap_or_else(|err| fail(format!("cannot read {}: {err}", path.display())));
+    text.parse()
+        .unwrap_or_else(|err| fail(format!("{} is not valid TOML: {err}", path.display())))
+}
 
 fn main() {
     let out = &PathBuf::from(env::var_os("OUT_DIR").unwrap());
@@ -11,4 +423,25 @@ fn main() {
         .unwrap();
     println!("cargo:rustc-link-search={}", out.display());
     println!("cargo:rerun-if-changed=memory.x");
+    if env::var_os("CARGO_FEATURE_DEFMT").is_some() {
+        println!("cargo:rustc-link-arg-bins=-Tdefmt.x");
+    }
+
+    println!("cargo:rerun-if-env-changed=BUTTON_CONFIG");
+    println!("cargo:rustc-check-cfg=cfg(button_toml_board)");
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let config = ButtonConfig::parse(&load(&manifest_dir));
+    if config.board.is_some() {
+        for feature in ["BOARD_PICO2", "BOARD_CARRIER"] {
+            if env::var_os(format!("CARGO_FEATURE_{feature}")).is_some() {
+                let feature = feature.to_lowercase().replace('_', "-");
+                fail(format!(
+                    "[board] conflicts with the `{feature}` feature; use one or the other"
+                ));
+            }
+        }
+        println!("cargo:rustc-cfg=button_toml_board");
+    }
+    fs::write(out.join("button_config.rs"), config.constants()).unwrap();
+    fs::write(out.join("board_pins.rs"), config.board_pins()).unwrap();
 }
@@ -1,5 +1,6 @@
 MEMORY {
-    FLASH : ORIGIN = 0x10000000, LENGTH = 4096K
+    FLASH : ORIGIN = 0x10000000, LENGTH = 4096K - 16K
+    SETTINGS : ORIGIN = 0x10000000 + 4096K - 16K, LENGTH = 16K
     RAM   : ORIGIN = 0x20000000, LENGTH = 512K
     SRAM8 : ORIGIN = 0x20080000, LENGTH = 4K
     SRAM9 : ORIGIN = 0x20081000, LENGTH = 4K
@@ -7,6 +8,9 @@ MEMORY {
 
 _stack_start = ORIGIN(RAM) + LENGTH(RAM);
 
+__settings_start = ORIGIN(SETTINGS);
+__settings_end = ORIGIN(SETTINGS) + LENGTH(SETTINGS);
+
 SECTIONS {
     .start_block : ALIGN(4)
     {
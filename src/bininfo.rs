@@ -0,0 +1,202 @@
+/*
+ * @file bininfo.rs
+ * @brief Picotool binary info entries for pin assignments
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: bininfo.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Picotool Pin Binary Info.
+//!
+//! BRIEF:
+//! Builds "pins with name" binary info entries in the layout picotool
+//! reads from `.bi_entries`, so `picotool info` lists the button and
+//! LED pins with their functions.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use core::ffi::{c_char, CStr};
+
+/// Binary info data type for a pin mask with a name.
+#[allow(dead_code)]
+pub const TYPE_PINS_WITH_NAME: u16 = 9;
+
+/// Builds a binary info tag from two characters.
+///
+/// # Arguments
+/// * `chars` - Tag characters
+///
+/// # Returns
+/// * `u16` - First character in the low byte, second in the high byte
+#[allow(dead_code)]
+pub const fn make_tag(chars: &[u8; 2]) -> u16 {
+    (chars[1] as u16) << 8 | chars[0] as u16
+}
+
+/// Tag of entries defined by Raspberry Pi.
+#[allow(dead_code)]
+pub const TAG_RASPBERRY_PI: u16 = make_tag(b"RP");
+
+/// Returns the mask bit of a GPIO.
+///
+/// # Arguments
+/// * `pin` - GPIO number
+///
+/// # Returns
+/// * `u32` - Mask with the pin set, or 0 if it does not fit
+#[allow(dead_code)]
+pub const fn pin_mask(pin: u8) -> u32 {
+    if pin < 32 {
+        1 << pin
+    } else {
+        0
+    }
+}
+
+/// Pins-with-name binary info entry.
+///
+/// # Details
+/// Matches `binary_info_pins_with_name_t` from the Pico SDK.
+///
+/// # Fields
+/// * `data_type` - Always `TYPE_PINS_WITH_NAME`
+/// * `tag` - Entry tag
+/// * `pin_mask` - GPIOs the name applies to
+/// * `label` - Null-terminated name
+#[repr(C)]
+#[allow(dead_code)]
+pub struct PinsWithName {
+    data_type: u16,
+    tag: u16,
+    pin_mask: u32,
+    label: *const c_char,
+}
+
+// SAFETY: the label points to a static C string that is never written.
+unsafe impl Sync for PinsWithName {}
+
+impl PinsWithName {
+    /// Creates entry naming a set of pins.
+    ///
+    /// # Arguments
+    /// * `pin_mask` - GPIOs the name applies to
+    /// * `label` - Name shown by picotool
+    ///
+    /// # Returns
+    /// * `Self` - New PinsWithName entry
+    #[allow(dead_code)]
+    pub const fn new(pin_mask: u32, label: &'static CStr) -> Self {
+        Self {
+            data_type: TYPE_PINS_WITH_NAME,
+            tag: TAG_RASPBERRY_PI,
+            pin_mask,
+            label: label.as_ptr(),
+        }
+    }
+
+    /// Returns the entry's address for the `.bi_entries` table.
+    ///
+    /// # Returns
+    /// * `EntryPtr` - Pointer to this entry
+    #[allow(dead_code)]
+    pub const fn addr(&'static self) -> EntryPtr {
+        EntryPtr(self as *const Self as *const u32)
+    }
+
+    /// Returns the pins the name applies to.
+    ///
+    /// # Returns
+    /// * `u32` - Pin mask
+    #[allow(dead_code)]
+    pub const fn pin_mask(&self) -> u32 {
+        self.pin_mask
+    }
+}
+
+/// Address of a binary info entry in `.bi_entries`.
+///
+/// # Details
+/// Same layout as the entry addresses the Embassy binary info macros
+/// produce, so both can share the section.
+#[repr(transparent)]
+#[allow(dead_code)]
+pub struct EntryPtr(*const u32);
+
+// SAFETY: only created from references to immutable statics.
+unsafe impl Sync for EntryPtr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::{align_of, offset_of, size_of};
+
+    static BUTTON: PinsWithName = PinsWithName::new(pin_mask(15), c"Button");
+
+    // ==================== Encoding Tests ====================
+
+    #[test]
+    fn test_raspberry_pi_tag() {
+        assert_eq!(TAG_RASPBERRY_PI, 0x5052);
+    }
+
+    #[test]
+    fn test_pin_mask() {
+        assert_eq!(pin_mask(0), 1);
+        assert_eq!(pin_mask(15), 0x8000);
+        assert_eq!(pin_mask(31), 0x8000_0000);
+        assert_eq!(pin_mask(u8::MAX), 0);
+    }
+
+    // ==================== Layout Tests ====================
+
+    #[test]
+    fn test_layout_matches_sdk() {
+        assert_eq!(offset_of!(PinsWithName, data_type), 0);
+        assert_eq!(offset_of!(PinsWithName, tag), 2);
+        assert_eq!(offset_of!(PinsWithName, pin_mask), 4);
+        assert_eq!(offset_of!(PinsWithName, label), 8);
+        assert_eq!(size_of::<EntryPtr>(), size_of::<*const u32>());
+    }
+
+    #[test]
+    fn test_entry_fields() {
+        assert_eq!(BUTTON.data_type, TYPE_PINS_WITH_NAME);
+        assert_eq!(BUTTON.pin_mask(), 1 << 15);
+        // SAFETY: the label was created from a static C string.
+        let label = unsafe { CStr::from_ptr(BUTTON.label) };
+        assert_eq!(label, c"Button");
+    }
+
+    #[test]
+    fn test_addr_points_at_entry() {
+        let ptr = BUTTON.addr();
+        assert_eq!(ptr.0 as usize, &BUTTON as *const _ as usize);
+        assert_eq!(ptr.0 as usize % align_of::<u32>(), 0);
+    }
+}
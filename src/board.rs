@@ -0,0 +1,391 @@
+/*
+ * @file board.rs
+ * @brief Board support profiles for button and LED pin mappings
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: board.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Board Support Profiles.
+//!
+//! BRIEF:
+//! Describes the button and LED wiring of each supported board in one
+//! place: pins, polarity and pull. The profile is selected by cargo
+//! feature and checked for consistency at compile time.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{InputConfig, InputConfigError};
+use crate::config::generated;
+
+/// Number of user GPIOs on the RP2350A.
+#[allow(dead_code)]
+pub const GPIO_COUNT: u8 = 30;
+
+/// Signal polarity of a button or LED.
+///
+/// # Variants
+/// * `ActiveLow` - Low level means pressed or lit
+/// * `ActiveHigh` - High level means pressed or lit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum Polarity {
+    ActiveLow,
+    ActiveHigh,
+}
+
+impl Polarity {
+    /// Returns true if a pin level means active.
+    ///
+    /// # Arguments
+    /// * `level_high` - true if the pin reads high
+    ///
+    /// # Returns
+    /// * `bool` - true if pressed or lit
+    #[allow(dead_code)]
+    pub const fn is_active(self, level_high: bool) -> bool {
+        match self {
+            Polarity::ActiveLow => !level_high,
+            Polarity::ActiveHigh => level_high,
+        }
+    }
+
+    /// Returns the pin level for an active or inactive state.
+    ///
+    /// # Arguments
+    /// * `active` - true for pressed or lit
+    ///
+    /// # Returns
+    /// * `bool` - true if the pin should be high
+    #[allow(dead_code)]
+    pub const fn level(self, active: bool) -> bool {
+        match self {
+            Polarity::ActiveLow => !active,
+            Polarity::ActiveHigh => active,
+        }
+    }
+
+    /// Maps a button level to the active-low convention of `ButtonController`.
+    ///
+    /// # Arguments
+    /// * `level_high` - true if the pin reads high
+    ///
+    /// # Returns
+    /// * `bool` - Level an active-low button would read
+    #[allow(dead_code)]
+    pub const fn to_active_low(self, level_high: bool) -> bool {
+        !self.is_active(level_high)
+    }
+}
+
+/// Internal pull resistor.
+///
+/// # Variants
+/// * `Up` - Pull-up to 3.3 V
+/// * `Down` - Pull-down to GND
+/// * `None` - No internal pull; the board provides one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum Pull {
+    Up,
+    Down,
+    None,
+}
+
+/// Button and LED wiring of a board.
+///
+/// # Fields
+/// * `name` - Board name for logs
+/// * `button_pin` - Button GPIO
+/// * `button_polarity` - Button level when pressed
+/// * `button_pull` - Internal pull on the button pin
+/// * `button_schmitt` - Schmitt-trigger hysteresis on the button pin
+/// * `button_bypass_sync` - Input synchronizer bypassed on the button pin
+/// * `led_pin` - LED GPIO
+/// * `led_polarity` - LED level when lit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct BoardProfile {
+    pub name: &'static str,
+    pub button_pin: u8,
+    pub button_polarity: Polarity,
+    pub button_pull: Pull,
+    pub button_schmitt: bool,
+    pub button_bypass_sync: bool,
+    pub led_pin: u8,
+    pub led_polarity: Polarity,
+}
+
+impl BoardProfile {
+    /// Returns true if the profile is consistent.
+    ///
+    /// # Details
+    /// Pins must exist and differ, and the button pad settings must
+    /// pass `InputConfig` validation: the pull must hold the button
+    /// inactive, and the synchronizer may only be bypassed with the
+    /// Schmitt trigger enabled.
+    ///
+    /// # Returns
+    /// * `bool` - true if valid
+    #[allow(dead_code)]
+    pub const fn is_valid(&self) -> bool {
+        self.button_pin < GPIO_COUNT
+            && self.led_pin < GPIO_COUNT
+            && self.led_pin != self.button_pin
+            && self.input_config().is_ok()
+    }
+
+    /// Returns the button input configuration.
+    ///
+    /// # Returns
+    /// * `Result<InputConfig, InputConfigError>` - Button pad configuration,
+    ///   or why the profile's pad settings are rejected
+    #[allow(dead_code)]
+    pub const fn input_config(&self) -> Result<InputConfig, InputConfigError> {
+        InputConfig::new(
+            self.button_polarity,
+            self.button_pull,
+            self.button_schmitt,
+            self.button_bypass_sync,
+        )
+    }
+}
+
+/// Raspberry Pi Pico 2 on the breadboard in the README.
+///
+/// # Details
+/// Button from GPIO15 to GND using the internal pull-up; LED with
+/// series resistor from GPIO16 to GND.
+#[allow(dead_code)]
+pub const PICO2: BoardProfile = BoardProfile {
+    name: "Pico 2",
+    button_pin: 15,
+    button_polarity: Polarity::ActiveLow,
+    button_pull: Pull::Up,
+    button_schmitt: true,
+    button_bypass_sync: false,
+    led_pin: 16,
+    led_polarity: Polarity::ActiveHigh,
+};
+
+/// Custom carrier board.
+///
+/// # Details
+/// Button on GPIO20 switching to 3.3 V with an external pull-down, LED
+/// on GPIO21 sinking current through the pin.
+#[allow(dead_code)]
+pub const CARRIER: BoardProfile = BoardProfile {
+    name: "carrier",
+    button_pin: 20,
+    button_polarity: Polarity::ActiveHigh,
+    button_pull: Pull::None,
+    button_schmitt: true,
+    button_bypass_sync: false,
+    led_pin: 21,
+    led_polarity: Polarity::ActiveLow,
+};
+
+#[cfg(all(feature = "board-pico2", feature = "board-carrier"))]
+compile_error!("select at most one board-* feature");
+
+/// Board profile selected by cargo feature.
+///
+/// # Details
+/// `board-carrier` selects the carrier profile; otherwise, or with
+/// `board-pico2`, the Pico 2 breadboard profile is used.
+#[cfg(feature = "board-carrier")]
+const FEATURE_BOARD: BoardProfile = CARRIER;
+
+/// Board profile selected by cargo feature.
+#[cfg(not(feature = "board-carrier"))]
+const FEATURE_BOARD: BoardProfile = PICO2;
+
+/// Board profile in use.
+///
+/// # Details
+/// A `[board]` section in `button.toml` takes precedence over the
+/// cargo feature; `build.rs` rejects setting both.
+#[allow(dead_code)]
+pub const BOARD: BoardProfile = match generated::BOARD {
+    Some(board) => board,
+    None => FEATURE_BOARD,
+};
+
+const _: () = assert!(BOARD.is_valid(), "invalid board profile");
+
+/// Parses the GPIO number from an Embassy pin name such as `PIN_15`.
+///
+/// # Details
+/// Usable in constant context, so firmware can check at compile time
+/// that the peripheral it takes matches the board profile.
+///
+/// # Arguments
+/// * `name` - Peripheral name
+///
+/// # Returns
+/// * `Option<u8>` - GPIO number, or None if the name is not a GPIO
+#[allow(dead_code)]
+pub const fn pin_number(name: &str) -> Option<u8> {
+    let bytes = name.as_bytes();
+    let prefix = b"PIN_";
+    if bytes.len() <= prefix.len() || bytes.len() > prefix.len() + 2 {
+        return None;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[i] != prefix[i] {
+            return None;
+        }
+        i += 1;
+    }
+    let mut number: u8 = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        number = number * 10 + (bytes[i] - b'0');
+        i += 1;
+    }
+    if number < GPIO_COUNT {
+        Some(number)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ==================== Polarity Tests ====================
+
+    #[test]
+    fn test_active_low_levels() {
+        assert!(Polarity::ActiveLow.is_active(false));
+        assert!(!Polarity::ActiveLow.is_active(true));
+        assert!(!Polarity::ActiveLow.level(true));
+        assert!(Polarity::ActiveLow.level(false));
+    }
+
+    #[test]
+    fn test_active_high_levels() {
+        assert!(Polarity::ActiveHigh.is_active(true));
+        assert!(Polarity::ActiveHigh.level(true));
+        assert!(!Polarity::ActiveHigh.level(false));
+    }
+
+    #[test]
+    fn test_to_active_low_maps_press_to_low() {
+        assert!(!Polarity::ActiveLow.to_active_low(false));
+        assert!(!Polarity::ActiveHigh.to_active_low(true));
+        assert!(Polarity::ActiveHigh.to_active_low(false));
+    }
+
+    // ==================== Pin Name Tests ====================
+
+    #[test]
+    fn test_pin_number_parses_gpio_names() {
+        assert_eq!(pin_number("PIN_0"), Some(0));
+        assert_eq!(pin_number("PIN_15"), Some(15));
+        assert_eq!(pin_number("PIN_29"), Some(29));
+    }
+
+    #[test]
+    fn test_pin_number_rejects_other_names() {
+        assert_eq!(pin_number("PIN_30"), None);
+        assert_eq!(pin_number("PIN_"), None);
+        assert_eq!(pin_number("PIN_1A"), None);
+        assert_eq!(pin_number("PWM_SLICE1"), None);
+        assert_eq!(pin_number("PIN_123"), None);
+    }
+
+    // ==================== Profile Tests ====================
+
+    #[test]
+    fn test_builtin_profiles_valid() {
+        for profile in [PICO2, CARRIER] {
+            assert!(profile.is_valid(), "{}", profile.name);
+        }
+    }
+
+    #[test]
+    fn test_pico2_matches_readme_wiring() {
+        assert_eq!(PICO2.button_pin, 15);
+        assert_eq!(PICO2.led_pin, 16);
+    }
+
+    #[test]
+    fn test_pull_against_polarity_invalid() {
+        let mut profile = PICO2;
+        profile.button_pull = Pull::Down;
+        assert!(!profile.is_valid());
+        profile = CARRIER;
+        profile.button_pull = Pull::Up;
+        assert!(!profile.is_valid());
+    }
+
+    #[test]
+    fn test_bypass_without_schmitt_invalid() {
+        let mut profile = PICO2;
+        profile.button_bypass_sync = true;
+        assert!(profile.is_valid());
+        profile.button_schmitt = false;
+        assert!(!profile.is_valid());
+        assert_eq!(
+            profile.input_config(),
+            Err(InputConfigError::BypassWithoutSchmitt)
+        );
+    }
+
+    #[test]
+    fn test_pad_settings_reach_input_config() {
+        let mut profile = CARRIER;
+        profile.button_bypass_sync = true;
+        let config = profile.input_config().unwrap();
+        assert_eq!(config.polarity, Polarity::ActiveHigh);
+        assert_eq!(config.pull, Pull::None);
+        assert!(config.schmitt && config.bypass_sync);
+        profile = PICO2;
+        profile.button_schmitt = false;
+        assert!(!profile.input_config().unwrap().schmitt);
+    }
+
+    #[test]
+    fn test_shared_or_missing_pins_invalid() {
+        let mut profile = PICO2;
+        profile.led_pin = profile.button_pin;
+        assert!(!profile.is_valid());
+        profile = PICO2;
+        profile.button_pin = GPIO_COUNT;
+        assert!(!profile.is_valid());
+    }
+}
@@ -0,0 +1,341 @@
+/*
+ * @file bootsel.rs
+ * @brief Button gesture for rebooting into the USB bootloader
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: bootsel.rs
+//!
+//! DESCRIPTION:
+//! RP2350 BOOTSEL Reboot Gesture.
+//!
+//! BRIEF:
+//! Detects a long hold of the user button, optionally only at power-up,
+//! and requests a reboot into the ROM USB bootloader on release.
+//! Provides a countdown LED pattern so users know when to let go.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::ButtonEvent;
+use crate::config::{BOOTSEL_BOOT_WINDOW_MS, BOOTSEL_HOLD_MS};
+use crate::led::{LedPattern, LedState};
+
+/// ROM reboot flag selecting the BOOTSEL (USB bootloader) boot type.
+#[allow(dead_code)]
+pub const REBOOT_TYPE_BOOTSEL: u32 = 0x0002;
+
+/// ROM reboot flag requesting no return on success.
+#[allow(dead_code)]
+pub const REBOOT_NO_RETURN_ON_SUCCESS: u32 = 0x0100;
+
+/// When the BOOTSEL hold gesture is accepted.
+///
+/// # Variants
+/// * `PowerUpHold` - Button must already be held at power-up
+/// * `AnyHold` - Gesture may start at any time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum BootselTrigger {
+    PowerUpHold,
+    AnyHold,
+}
+
+/// BOOTSEL gesture configuration.
+///
+/// # Fields
+/// * `trigger` - When the gesture is accepted
+/// * `hold_ms` - Hold time required to arm the reboot
+/// * `boot_window_ms` - Time after start in which a power-up press must register
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct BootselConfig {
+    pub trigger: BootselTrigger,
+    pub hold_ms: u64,
+    pub boot_window_ms: u64,
+}
+
+impl Default for BootselConfig {
+    /// Returns power-up hold configuration from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default BOOTSEL configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            trigger: BootselTrigger::PowerUpHold,
+            hold_ms: BOOTSEL_HOLD_MS,
+            boot_window_ms: BOOTSEL_BOOT_WINDOW_MS,
+        }
+    }
+}
+
+/// BOOTSEL gesture state.
+///
+/// # Variants
+/// * `Waiting` - No qualifying press yet
+/// * `Holding` - Press in progress since the given timestamp
+/// * `Armed` - Hold time reached; release to reboot
+/// * `Disabled` - Gesture no longer possible
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum BootselState {
+    Waiting,
+    Holding(u64),
+    Armed,
+    Disabled,
+}
+
+/// BOOTSEL gesture detector.
+///
+/// # Details
+/// Consumes debounced events from `ButtonController`. The reboot is
+/// requested on release after the hold time so the button is not held
+/// while the bootloader starts.
+///
+/// # Fields
+/// * `config` - Gesture configuration
+/// * `state` - Current gesture state
+/// * `start_ms` - Timestamp the detector was created
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct BootselDetector {
+    config: BootselConfig,
+    state: BootselState,
+    start_ms: u64,
+}
+
+impl BootselDetector {
+    /// Creates detector starting at `now_ms`.
+    ///
+    /// # Arguments
+    /// * `config` - Gesture configuration
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Self` - New BootselDetector instance
+    #[allow(dead_code)]
+    pub fn new(config: BootselConfig, now_ms: u64) -> Self {
+        Self {
+            config,
+            state: BootselState::Waiting,
+            start_ms: now_ms,
+        }
+    }
+
+    /// Advances the gesture with the latest button event.
+    ///
+    /// # Arguments
+    /// * `event` - Debounced event from this sample, if any
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `bool` - true if the device should reboot into BOOTSEL now
+    #[allow(dead_code)]
+    pub fn update(&mut self, event: Option<ButtonEvent>, now_ms: u64) -> bool {
+        let power_up = self.config.trigger == BootselTrigger::PowerUpHold;
+        self.state = match (self.state, event) {
+            (BootselState::Waiting, _)
+                if power_up
+                    && now_ms.saturating_sub(self.start_ms) > self.config.boot_window_ms =>
+            {
+                BootselState::Disabled
+            }
+            (BootselState::Waiting, Some(ButtonEvent::Pressed)) => BootselState::Holding(now_ms),
+            (BootselState::Holding(_), Some(ButtonEvent::Released)) if power_up => {
+                BootselState::Disabled
+            }
+            (BootselState::Holding(_), Some(ButtonEvent::Released)) => BootselState::Waiting,
+            (BootselState::Holding(since), _)
+                if now_ms.saturating_sub(since) >= self.config.hold_ms =>
+            {
+                info!("bootsel: armed, release to reboot");
+                BootselState::Armed
+            }
+            (BootselState::Armed, Some(ButtonEvent::Released)) => {
+                self.state = BootselState::Disabled;
+                return true;
+            }
+            (state, _) => state,
+        };
+        false
+    }
+
+    /// Returns current gesture state.
+    ///
+    /// # Returns
+    /// * `BootselState` - Gesture state
+    #[allow(dead_code)]
+    pub fn state(&self) -> BootselState {
+        self.state
+    }
+
+    /// Returns LED feedback for the gesture.
+    ///
+    /// # Details
+    /// While holding, blinks faster as the countdown nears zero
+    /// (one step per second). When armed, the LED stays on.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<LedPattern>` - Pattern to show, None when inactive
+    #[allow(dead_code)]
+    pub fn led_pattern(&self, now_ms: u64) -> Option<LedPattern> {
+        match self.state {
+            BootselState::Holding(since) => {
+                let remaining = self
+                    .config
+                    .hold_ms
+                    .saturating_sub(now_ms.saturating_sub(since));
+                let seconds = remaining.div_ceil(1000).clamp(1, 10) as u32;
+                Some(LedPattern::Blink {
+                    on_ms: 50 * seconds,
+                    off_ms: 50 * seconds,
+                })
+            }
+            BootselState::Armed => Some(LedPattern::Solid(LedState::On)),
+            BootselState::Waiting | BootselState::Disabled => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(trigger: BootselTrigger) -> BootselConfig {
+        BootselConfig {
+            trigger,
+            hold_ms: 3000,
+            boot_window_ms: 100,
+        }
+    }
+
+    fn hold(det: &mut BootselDetector, from: u64, to: u64) {
+        let mut t = from;
+        while t <= to {
+            assert!(!det.update(None, t));
+            t += 10;
+        }
+    }
+
+    // ==================== Power-Up Hold Tests ====================
+
+    #[test]
+    fn test_default_config_is_power_up() {
+        let cfg = BootselConfig::default();
+        assert_eq!(cfg.trigger, BootselTrigger::PowerUpHold);
+        assert_eq!(cfg.hold_ms, BOOTSEL_HOLD_MS);
+    }
+
+    #[test]
+    fn test_power_up_hold_then_release_reboots() {
+        let mut det = BootselDetector::new(config(BootselTrigger::PowerUpHold), 0);
+        assert!(!det.update(Some(ButtonEvent::Pressed), 30));
+        hold(&mut det, 40, 3030);
+        assert_eq!(det.state(), BootselState::Armed);
+        assert!(det.update(Some(ButtonEvent::Released), 3100));
+        assert_eq!(det.state(), BootselState::Disabled);
+    }
+
+    #[test]
+    fn test_early_release_cancels() {
+        let mut det = BootselDetector::new(config(BootselTrigger::PowerUpHold), 0);
+        det.update(Some(ButtonEvent::Pressed), 30);
+        hold(&mut det, 40, 2000);
+        assert!(!det.update(Some(ButtonEvent::Released), 2010));
+        assert_eq!(det.state(), BootselState::Disabled);
+    }
+
+    #[test]
+    fn test_press_after_boot_window_ignored() {
+        let mut det = BootselDetector::new(config(BootselTrigger::PowerUpHold), 0);
+        det.update(None, 50);
+        det.update(Some(ButtonEvent::Pressed), 500);
+        assert_eq!(det.state(), BootselState::Disabled);
+        hold(&mut det, 510, 5000);
+        assert!(!det.update(Some(ButtonEvent::Released), 5010));
+    }
+
+    #[test]
+    fn test_disabled_is_permanent() {
+        let mut det = BootselDetector::new(config(BootselTrigger::PowerUpHold), 0);
+        det.update(None, 200);
+        assert!(!det.update(Some(ButtonEvent::Pressed), 210));
+        assert_eq!(det.state(), BootselState::Disabled);
+    }
+
+    // ==================== Any-Time Hold Tests ====================
+
+    #[test]
+    fn test_any_hold_later_press_reboots() {
+        let mut det = BootselDetector::new(config(BootselTrigger::AnyHold), 0);
+        det.update(None, 10_000);
+        det.update(Some(ButtonEvent::Pressed), 10_010);
+        hold(&mut det, 10_020, 13_010);
+        assert!(det.update(Some(ButtonEvent::Released), 13_020));
+    }
+
+    #[test]
+    fn test_any_hold_short_press_returns_to_waiting() {
+        let mut det = BootselDetector::new(config(BootselTrigger::AnyHold), 0);
+        det.update(Some(ButtonEvent::Pressed), 10);
+        det.update(Some(ButtonEvent::Released), 500);
+        assert_eq!(det.state(), BootselState::Waiting);
+    }
+
+    // ==================== LED Feedback Tests ====================
+
+    #[test]
+    fn test_no_feedback_when_waiting() {
+        let det = BootselDetector::new(config(BootselTrigger::AnyHold), 0);
+        assert_eq!(det.led_pattern(0), None);
+    }
+
+    #[test]
+    fn test_countdown_blinks_faster() {
+        let mut det = BootselDetector::new(config(BootselTrigger::PowerUpHold), 0);
+        det.update(Some(ButtonEvent::Pressed), 0);
+        let early = det.led_pattern(0).unwrap().period_ms();
+        let late = det.led_pattern(2500).unwrap().period_ms();
+        assert!(late < early);
+    }
+
+    #[test]
+    fn test_armed_feedback_solid_on() {
+        let mut det = BootselDetector::new(config(BootselTrigger::PowerUpHold), 0);
+        det.update(Some(ButtonEvent::Pressed), 0);
+        det.update(None, 3000);
+        assert_eq!(det.led_pattern(3000), Some(LedPattern::Solid(LedState::On)));
+    }
+}
@@ -38,26 +38,206 @@
 //!
 //! AUTHOR: Kevin Thomas
 //! CREATION DATE: December 5, 2025
-//! UPDATE DATE: December 5, 2025
+//! UPDATE DATE: October 18, 2026
 
-use crate::config::DEBOUNCE_COUNT;
+use crate::board::{Polarity, Pull};
+use crate::config::{DEBOUNCE_COUNT, DEBOUNCE_STABLE_US};
+
+/// Debounced button event.
+///
+/// # Details
+/// Emitted by `ButtonController::update` when the debounced state changes.
+///
+/// # Variants
+/// * `Pressed` - Button transitioned from released to pressed
+/// * `Released` - Button transitioned from pressed to released
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum ButtonEvent {
+    Pressed,
+    Released,
+}
+
+/// How a debouncer turns raw samples into events.
+///
+/// # Details
+/// `Conservative` waits for the input to hold a new level for the full
+/// window before reporting it, so glitches never produce events but
+/// every edge is delayed by the window. `Eager` reports the first
+/// changed sample and then ignores the input for the window, so edges
+/// have no added latency but a single-sample glitch is reported as a
+/// short press.
+///
+/// # Variants
+/// * `Conservative` - Report a level once it has been stable
+/// * `Eager` - Report the first change, then lock out for the window
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum DebounceStrategy {
+    Conservative,
+    Eager,
+}
+
+/// Reason an input pad configuration is rejected.
+///
+/// # Variants
+/// * `PullHoldsPressed` - The pull drives the pin to its pressed level
+/// * `BypassWithoutSchmitt` - Synchronizer bypassed with no hysteresis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum InputConfigError {
+    PullHoldsPressed,
+    BypassWithoutSchmitt,
+}
+
+/// Button input pad configuration.
+///
+/// # Details
+/// Portable description of how the button GPIO is set up; the
+/// firmware applies it to the pad. Bypassing the input synchronizer
+/// saves two system clock cycles of latency but passes asynchronous
+/// edges straight to the core, so it is only allowed with the Schmitt
+/// trigger enabled to keep slow edges clean.
+///
+/// # Fields
+/// * `polarity` - Button level when pressed
+/// * `pull` - Internal pull resistor
+/// * `schmitt` - Schmitt-trigger input hysteresis enabled
+/// * `bypass_sync` - Input synchronizer bypassed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct InputConfig {
+    pub polarity: Polarity,
+    pub pull: Pull,
+    pub schmitt: bool,
+    pub bypass_sync: bool,
+}
+
+impl Default for InputConfig {
+    /// Returns configuration for an active-low button.
+    ///
+    /// # Returns
+    /// * `Self` - Default input configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::for_polarity(Polarity::ActiveLow)
+    }
+}
+
+impl InputConfig {
+    /// Creates a validated input configuration.
+    ///
+    /// # Arguments
+    /// * `polarity` - Button level when pressed
+    /// * `pull` - Internal pull resistor
+    /// * `schmitt` - Enable Schmitt-trigger input hysteresis
+    /// * `bypass_sync` - Bypass the input synchronizer
+    ///
+    /// # Returns
+    /// * `Result<Self, InputConfigError>` - Configuration, or why it is rejected
+    #[allow(dead_code)]
+    pub const fn new(
+        polarity: Polarity,
+        pull: Pull,
+        schmitt: bool,
+        bypass_sync: bool,
+    ) -> Result<Self, InputConfigError> {
+        let config = Self {
+            polarity,
+            pull,
+            schmitt,
+            bypass_sync,
+        };
+        match config.validate() {
+            Ok(()) => Ok(config),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns sensible defaults for a button polarity.
+    ///
+    /// # Details
+    /// Pulls the pin to its released level, enables the Schmitt
+    /// trigger and keeps the input synchronizer.
+    ///
+    /// # Arguments
+    /// * `polarity` - Button level when pressed
+    ///
+    /// # Returns
+    /// * `Self` - Input configuration
+    #[allow(dead_code)]
+    pub const fn for_polarity(polarity: Polarity) -> Self {
+        Self {
+            polarity,
+            pull: match polarity {
+                Polarity::ActiveLow => Pull::Up,
+                Polarity::ActiveHigh => Pull::Down,
+            },
+            schmitt: true,
+            bypass_sync: false,
+        }
+    }
+
+    /// Checks the configuration.
+    ///
+    /// # Returns
+    /// * `Result<(), InputConfigError>` - Ok, or the first problem found
+    #[allow(dead_code)]
+    pub const fn validate(&self) -> Result<(), InputConfigError> {
+        if matches!(
+            (self.polarity, self.pull),
+            (Polarity::ActiveLow, Pull::Down) | (Polarity::ActiveHigh, Pull::Up)
+        ) {
+            return Err(InputConfigError::PullHoldsPressed);
+        }
+        if self.bypass_sync && !self.schmitt {
+            return Err(InputConfigError::BypassWithoutSchmitt);
+        }
+        Ok(())
+    }
+
+    /// Maps a pin level to the active-low convention of `ButtonController`.
+    ///
+    /// # Arguments
+    /// * `level_high` - true if the pin reads high
+    ///
+    /// # Returns
+    /// * `bool` - Level an active-low button would read
+    #[allow(dead_code)]
+    pub const fn to_active_low(&self, level_high: bool) -> bool {
+        self.polarity.to_active_low(level_high)
+    }
+}
 
 /// Button controller with debouncing.
 ///
 /// # Details
 /// Maintains button state with software debouncing.
 /// Uses sample-based debouncing for reliable detection.
+/// In eager mode the counter instead tracks samples since the last
+/// reported edge, and the threshold is the lock-out length.
 ///
 /// # Fields
 /// * `pressed` - Current debounced button state (true = pressed)
 /// * `raw_pressed` - Current raw (unfiltered) state
 /// * `debounce_count` - Current debounce counter
+/// * `threshold` - Stable samples required for a state change
+/// * `bounces` - Raw transitions rejected before reaching the threshold
+/// * `strategy` - Conservative or eager debouncing
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(dead_code)]
 pub struct ButtonController {
     pressed: bool,
     raw_pressed: bool,
     debounce_count: u32,
+    threshold: u32,
+    bounces: u32,
+    strategy: DebounceStrategy,
 }
 
 impl Default for ButtonController {
@@ -84,10 +264,51 @@ impl ButtonController {
     /// * `Self` - New ButtonController instance
     #[allow(dead_code)]
     pub fn new() -> Self {
+        Self::with_debounce_count(DEBOUNCE_COUNT)
+    }
+
+    /// Creates new button controller with custom debounce threshold.
+    ///
+    /// # Details
+    /// Initializes controller with button released state.
+    /// A threshold of zero is treated as one.
+    ///
+    /// # Arguments
+    /// * `count` - Stable samples required for a state change
+    ///
+    /// # Returns
+    /// * `Self` - New ButtonController instance
+    #[allow(dead_code)]
+    pub fn with_debounce_count(count: u32) -> Self {
+        Self::with_strategy(DebounceStrategy::Conservative, count)
+    }
+
+    /// Creates new button controller with a debounce strategy.
+    ///
+    /// # Details
+    /// For `Eager`, `count` is the number of samples ignored after each
+    /// reported edge, and the controller starts outside the lock-out.
+    /// A count of zero is treated as one.
+    ///
+    /// # Arguments
+    /// * `strategy` - Conservative or eager debouncing
+    /// * `count` - Stable samples or lock-out samples
+    ///
+    /// # Returns
+    /// * `Self` - New ButtonController instance
+    #[allow(dead_code)]
+    pub fn with_strategy(strategy: DebounceStrategy, count: u32) -> Self {
+        let threshold = count.max(1);
         Self {
             pressed: false,
             raw_pressed: false,
-            debounce_count: 0,
+            debounce_count: match strategy {
+                DebounceStrategy::Conservative => 0,
+                DebounceStrategy::Eager => threshold,
+            },
+            threshold,
+            bounces: 0,
+            strategy,
         }
     }
 
@@ -96,23 +317,112 @@ impl ButtonController {
     /// # Details
     /// Processes raw GPIO input through debounce filter.
     /// Active-low: false (low GPIO) means pressed.
+    /// A raw change that abandons an unconfirmed transition counts as a
+    /// bounce; in eager mode, every raw change during lock-out does.
     ///
     /// # Arguments
     /// * `gpio_high` - true if GPIO high (released), false if low (pressed)
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event if the debounced state changed
     #[allow(dead_code)]
-    pub fn update(&mut self, gpio_high: bool) {
+    pub fn update(&mut self, gpio_high: bool) -> Option<ButtonEvent> {
         let new_raw = !gpio_high;
+        if self.strategy == DebounceStrategy::Eager {
+            return self.update_eager(new_raw);
+        }
         if new_raw == self.raw_pressed {
-            if self.debounce_count < DEBOUNCE_COUNT {
+            if self.debounce_count < self.threshold {
                 self.debounce_count += 1;
             }
         } else {
+            if self.raw_pressed != self.pressed {
+                self.bounces = self.bounces.saturating_add(1);
+            }
             self.raw_pressed = new_raw;
             self.debounce_count = 0;
         }
-        if self.debounce_count >= DEBOUNCE_COUNT {
+        if self.debounce_count >= self.threshold && self.pressed != self.raw_pressed {
             self.pressed = self.raw_pressed;
+            let event = if self.pressed {
+                ButtonEvent::Pressed
+            } else {
+                ButtonEvent::Released
+            };
+            debug!("button: {}", event);
+            return Some(event);
+        }
+        None
+    }
+
+    /// Eager update: report the first change, then lock out.
+    ///
+    /// # Details
+    /// The `threshold` samples after a reported edge are ignored. A
+    /// level that differs from the debounced state when the lock-out
+    /// ends is reported on the next sample.
+    ///
+    /// # Arguments
+    /// * `new_raw` - true if the raw input is pressed
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event if the debounced state changed
+    fn update_eager(&mut self, new_raw: bool) -> Option<ButtonEvent> {
+        let changed = new_raw != self.raw_pressed;
+        self.raw_pressed = new_raw;
+        if self.debounce_count < self.threshold {
+            self.debounce_count += 1;
+            if changed {
+                self.bounces = self.bounces.saturating_add(1);
+            }
+            return None;
+        }
+        if new_raw == self.pressed {
+            return None;
+        }
+        self.pressed = new_raw;
+        self.debounce_count = 0;
+        let event = if self.pressed {
+            ButtonEvent::Pressed
+        } else {
+            ButtonEvent::Released
+        };
+        debug!("button: {}", event);
+        Some(event)
+    }
+
+    /// Forces the debounced state to pressed.
+    ///
+    /// # Details
+    /// Used when a press is confirmed by other means, such as the
+    /// button edge that woke the device from sleep. The debounce
+    /// counter is saturated so the press is not re-filtered; in eager
+    /// mode a new press starts the lock-out instead.
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Pressed if the button was released
+    #[allow(dead_code)]
+    pub fn resume_pressed(&mut self) -> Option<ButtonEvent> {
+        self.raw_pressed = true;
+        self.debounce_count = self.threshold;
+        if self.pressed {
+            return None;
         }
+        self.pressed = true;
+        if self.strategy == DebounceStrategy::Eager {
+            self.debounce_count = 0;
+        }
+        debug!("button: {}", ButtonEvent::Pressed);
+        Some(ButtonEvent::Pressed)
+    }
+
+    /// Returns the debounce strategy.
+    ///
+    /// # Returns
+    /// * `DebounceStrategy` - Conservative or eager debouncing
+    #[allow(dead_code)]
+    pub fn strategy(&self) -> DebounceStrategy {
+        self.strategy
     }
 
     /// Returns true if button is pressed.
@@ -126,12 +436,386 @@ impl ButtonController {
     pub fn is_pressed(&self) -> bool {
         self.pressed
     }
+
+    /// Returns true if the latest raw sample was pressed.
+    ///
+    /// # Details
+    /// Undebounced; may be a bounce.
+    ///
+    /// # Returns
+    /// * `bool` - true if the raw input is pressed
+    #[allow(dead_code)]
+    pub fn is_raw_pressed(&self) -> bool {
+        self.raw_pressed
+    }
+
+    /// Returns true if the raw input agrees with the debounced state.
+    ///
+    /// # Details
+    /// True once the raw input has matched the debounced state for the
+    /// full threshold, meaning no transition is pending.
+    ///
+    /// # Returns
+    /// * `bool` - true if settled
+    #[allow(dead_code)]
+    pub fn is_settled(&self) -> bool {
+        self.raw_pressed == self.pressed && self.debounce_count >= self.threshold
+    }
+
+    /// Returns number of bounces rejected by the debouncer.
+    ///
+    /// # Details
+    /// Counts raw transitions that reverted before being confirmed.
+    /// Saturates at `u32::MAX`.
+    ///
+    /// # Returns
+    /// * `u32` - Rejected bounce count
+    #[allow(dead_code)]
+    pub fn bounces_rejected(&self) -> u32 {
+        self.bounces
+    }
+}
+
+/// Time-based button controller with debouncing.
+///
+/// # Details
+/// Same filter as `ButtonController`, but the raw input must hold a
+/// new level for a duration rather than a number of samples, so the
+/// window does not depend on the sampling interval. A level change is
+/// reported on the first sample taken at least `stable_us` after the
+/// change was first seen. At a fixed interval `T` it behaves exactly
+/// like a count-based controller with `stable_us = count * T`, in
+/// either strategy. In eager mode `raw_since_us` is the time of the
+/// last reported edge and `held` means the lock-out has ended.
+///
+/// # Fields
+/// * `pressed` - Current debounced button state (true = pressed)
+/// * `raw_pressed` - Current raw (unfiltered) state
+/// * `raw_since_us` - Time the raw state was first seen, if sampled
+/// * `held` - Raw state has held for the full window
+/// * `stable_us` - Time the raw state must hold for a state change
+/// * `bounces` - Raw transitions rejected before reaching the window
+/// * `strategy` - Conservative or eager debouncing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct TimedButtonController {
+    pressed: bool,
+    raw_pressed: bool,
+    raw_since_us: Option<u64>,
+    held: bool,
+    stable_us: u64,
+    bounces: u32,
+    strategy: DebounceStrategy,
+}
+
+impl Default for TimedButtonController {
+    /// Returns default TimedButtonController instance.
+    ///
+    /// # Details
+    /// Delegates to new() for initialization.
+    ///
+    /// # Returns
+    /// * `Self` - New TimedButtonController with default values
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimedButtonController {
+    /// Creates new time-based button controller.
+    ///
+    /// # Details
+    /// Uses the `DEBOUNCE_STABLE_US` window.
+    ///
+    /// # Returns
+    /// * `Self` - New TimedButtonController instance
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self::with_stable_us(DEBOUNCE_STABLE_US)
+    }
+
+    /// Creates new time-based button controller with a custom window.
+    ///
+    /// # Details
+    /// Initializes controller with button released state.
+    /// A window of zero reports every change on the sample after it.
+    ///
+    /// # Arguments
+    /// * `stable_us` - Microseconds the input must hold for a state change
+    ///
+    /// # Returns
+    /// * `Self` - New TimedButtonController instance
+    #[allow(dead_code)]
+    pub fn with_stable_us(stable_us: u64) -> Self {
+        Self::with_strategy(DebounceStrategy::Conservative, stable_us)
+    }
+
+    /// Creates new time-based button controller with a debounce strategy.
+    ///
+    /// # Details
+    /// For `Eager`, samples up to `stable_us` after each reported edge
+    /// are ignored, and the controller starts outside the lock-out.
+    ///
+    /// # Arguments
+    /// * `strategy` - Conservative or eager debouncing
+    /// * `stable_us` - Stability window or lock-out in microseconds
+    ///
+    /// # Returns
+    /// * `Self` - New TimedButtonController instance
+    #[allow(dead_code)]
+    pub fn with_strategy(strategy: DebounceStrategy, stable_us: u64) -> Self {
+        Self {
+            pressed: false,
+            raw_pressed: false,
+            raw_since_us: None,
+            held: strategy == DebounceStrategy::Eager,
+            stable_us,
+            bounces: 0,
+            strategy,
+        }
+    }
+
+    /// Updates button state with a timestamped GPIO sample.
+    ///
+    /// # Details
+    /// Active-low: false (low GPIO) means pressed. Timestamps should
+    /// not decrease; a sample earlier than the raw change is treated
+    /// as no time elapsed. A raw change that abandons an unconfirmed
+    /// transition counts as a bounce; in eager mode, every raw change
+    /// during lock-out does.
+    ///
+    /// # Arguments
+    /// * `gpio_high` - true if GPIO high (released), false if low (pressed)
+    /// * `now_us` - Sample time in microseconds
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event if the debounced state changed
+    #[allow(dead_code)]
+    pub fn update(&mut self, gpio_high: bool, now_us: u64) -> Option<ButtonEvent> {
+        let new_raw = !gpio_high;
+        if self.strategy == DebounceStrategy::Eager {
+            return self.update_eager(new_raw, now_us);
+        }
+        if new_raw != self.raw_pressed {
+            if self.raw_pressed != self.pressed {
+                self.bounces = self.bounces.saturating_add(1);
+            }
+            self.raw_pressed = new_raw;
+            self.raw_since_us = Some(now_us);
+            self.held = false;
+            return None;
+        }
+        let since = *self.raw_since_us.get_or_insert(now_us);
+        self.held |= now_us.saturating_sub(since) >= self.stable_us;
+        if self.held && self.pressed != self.raw_pressed {
+            self.pressed = self.raw_pressed;
+            let event = if self.pressed {
+                ButtonEvent::Pressed
+            } else {
+                ButtonEvent::Released
+            };
+            debug!("button: {}", event);
+            return Some(event);
+        }
+        None
+    }
+
+    /// Eager update: report the first change, then lock out.
+    ///
+    /// # Details
+    /// Samples up to `stable_us` after a reported edge are ignored. A
+    /// level that differs from the debounced state when the lock-out
+    /// ends is reported on the next sample.
+    ///
+    /// # Arguments
+    /// * `new_raw` - true if the raw input is pressed
+    /// * `now_us` - Sample time in microseconds
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event if the debounced state changed
+    fn update_eager(&mut self, new_raw: bool, now_us: u64) -> Option<ButtonEvent> {
+        let changed = new_raw != self.raw_pressed;
+        self.raw_pressed = new_raw;
+        if !self.held {
+            let edge = *self.raw_since_us.get_or_insert(now_us);
+            if now_us.saturating_sub(edge) <= self.stable_us {
+                if changed {
+                    self.bounces = self.bounces.saturating_add(1);
+                }
+                return None;
+            }
+            self.held = true;
+        }
+        if new_raw == self.pressed {
+            return None;
+        }
+        self.pressed = new_raw;
+        self.raw_since_us = Some(now_us);
+        self.held = false;
+        let event = if self.pressed {
+            ButtonEvent::Pressed
+        } else {
+            ButtonEvent::Released
+        };
+        debug!("button: {}", event);
+        Some(event)
+    }
+
+    /// Forces the debounced state to pressed.
+    ///
+    /// # Details
+    /// Used when a press is confirmed by other means, such as the
+    /// button edge that woke the device from sleep. The raw state is
+    /// marked as held so the press is not re-filtered; in eager mode a
+    /// new press starts the lock-out instead.
+    ///
+    /// # Arguments
+    /// * `now_us` - Current time in microseconds
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Pressed if the button was released
+    #[allow(dead_code)]
+    pub fn resume_pressed(&mut self, now_us: u64) -> Option<ButtonEvent> {
+        self.raw_pressed = true;
+        self.raw_since_us = Some(now_us);
+        self.held = true;
+        if self.pressed {
+            return None;
+        }
+        self.pressed = true;
+        if self.strategy == DebounceStrategy::Eager {
+            self.held = false;
+        }
+        debug!("button: {}", ButtonEvent::Pressed);
+        Some(ButtonEvent::Pressed)
+    }
+
+    /// Returns the debounce strategy.
+    ///
+    /// # Returns
+    /// * `DebounceStrategy` - Conservative or eager debouncing
+    #[allow(dead_code)]
+    pub fn strategy(&self) -> DebounceStrategy {
+        self.strategy
+    }
+
+    /// Returns true if button is pressed.
+    ///
+    /// # Returns
+    /// * `bool` - true if button is pressed
+    #[allow(dead_code)]
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Returns true if the raw input agrees with the debounced state.
+    ///
+    /// # Details
+    /// True once the raw input has matched the debounced state for the
+    /// full window as of the latest sample.
+    ///
+    /// # Returns
+    /// * `bool` - true if settled
+    #[allow(dead_code)]
+    pub fn is_settled(&self) -> bool {
+        self.raw_pressed == self.pressed && self.held
+    }
+
+    /// Returns number of bounces rejected by the debouncer.
+    ///
+    /// # Details
+    /// Counts raw transitions that reverted before being confirmed.
+    /// Saturates at `u32::MAX`.
+    ///
+    /// # Returns
+    /// * `u32` - Rejected bounce count
+    #[allow(dead_code)]
+    pub fn bounces_rejected(&self) -> u32 {
+        self.bounces
+    }
+
+    /// Returns the stability window.
+    ///
+    /// # Returns
+    /// * `u64` - Microseconds the input must hold for a state change
+    #[allow(dead_code)]
+    pub fn stable_us(&self) -> u64 {
+        self.stable_us
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ==================== InputConfig Tests ====================
+
+    #[test]
+    fn test_input_defaults_from_polarity() {
+        let low = InputConfig::for_polarity(Polarity::ActiveLow);
+        let high = InputConfig::for_polarity(Polarity::ActiveHigh);
+        assert_eq!(low.pull, Pull::Up);
+        assert_eq!(high.pull, Pull::Down);
+        assert!(low.schmitt && !low.bypass_sync);
+        assert_eq!(InputConfig::default(), low);
+        assert_eq!(low.validate(), Ok(()));
+        assert_eq!(high.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_input_pull_against_polarity_rejected() {
+        let mut config = InputConfig::for_polarity(Polarity::ActiveLow);
+        config.pull = Pull::Down;
+        assert_eq!(config.validate(), Err(InputConfigError::PullHoldsPressed));
+        config = InputConfig::for_polarity(Polarity::ActiveHigh);
+        config.pull = Pull::Up;
+        assert_eq!(config.validate(), Err(InputConfigError::PullHoldsPressed));
+        config.pull = Pull::None;
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_input_bypass_requires_schmitt() {
+        let mut config = InputConfig {
+            bypass_sync: true,
+            ..InputConfig::default()
+        };
+        assert_eq!(config.validate(), Ok(()));
+        config.schmitt = false;
+        assert_eq!(
+            config.validate(),
+            Err(InputConfigError::BypassWithoutSchmitt)
+        );
+    }
+
+    #[test]
+    fn test_input_new_validates() {
+        let config = InputConfig::new(Polarity::ActiveHigh, Pull::None, true, true).unwrap();
+        assert_eq!(config.pull, Pull::None);
+        assert!(config.schmitt && config.bypass_sync);
+        assert_eq!(
+            InputConfig::new(Polarity::ActiveLow, Pull::Up, false, true),
+            Err(InputConfigError::BypassWithoutSchmitt)
+        );
+        assert_eq!(
+            InputConfig::new(Polarity::ActiveLow, Pull::Down, true, false),
+            Err(InputConfigError::PullHoldsPressed)
+        );
+    }
+
+    #[test]
+    fn test_input_level_mapping() {
+        let high = InputConfig::for_polarity(Polarity::ActiveHigh);
+        let mut ctrl = ButtonController::with_debounce_count(1);
+        ctrl.update(high.to_active_low(true));
+        assert_eq!(
+            ctrl.update(high.to_active_low(true)),
+            Some(ButtonEvent::Pressed)
+        );
+    }
+
     // ==================== ButtonController Construction Tests ====================
 
     #[test]
@@ -147,6 +831,30 @@ mod tests {
         assert_eq!(default.is_pressed(), new.is_pressed());
     }
 
+    #[test]
+    fn test_with_default_count_equals_new() {
+        assert_eq!(
+            ButtonController::with_debounce_count(DEBOUNCE_COUNT),
+            ButtonController::new()
+        );
+    }
+
+    #[test]
+    fn test_custom_debounce_count() {
+        let mut ctrl = ButtonController::with_debounce_count(2);
+        ctrl.update(false);
+        ctrl.update(false);
+        assert!(!ctrl.is_pressed());
+        assert_eq!(ctrl.update(false), Some(ButtonEvent::Pressed));
+    }
+
+    #[test]
+    fn test_zero_debounce_count_clamped() {
+        let mut ctrl = ButtonController::with_debounce_count(0);
+        ctrl.update(false);
+        assert_eq!(ctrl.update(false), Some(ButtonEvent::Pressed));
+    }
+
     // ==================== Debounce Logic Tests ====================
 
     #[test]
@@ -192,6 +900,21 @@ mod tests {
         assert!(!ctrl.is_pressed());
     }
 
+    #[test]
+    fn test_settled_after_threshold() {
+        let mut ctrl = ButtonController::with_debounce_count(2);
+        assert!(!ctrl.is_settled());
+        ctrl.update(true);
+        ctrl.update(true);
+        assert!(ctrl.is_settled());
+        ctrl.update(false);
+        assert!(!ctrl.is_settled());
+        ctrl.update(false);
+        ctrl.update(false);
+        assert!(ctrl.is_pressed());
+        assert!(ctrl.is_settled());
+    }
+
     // ==================== Bounce Rejection Tests ====================
 
     #[test]
@@ -204,6 +927,36 @@ mod tests {
         assert!(!ctrl.is_pressed());
     }
 
+    #[test]
+    fn test_bounce_counted_when_transition_abandoned() {
+        let mut ctrl = ButtonController::new();
+        ctrl.update(false);
+        ctrl.update(true);
+        assert_eq!(ctrl.bounces_rejected(), 1);
+    }
+
+    #[test]
+    fn test_clean_press_has_no_bounces() {
+        let mut ctrl = ButtonController::new();
+        for _ in 0..=DEBOUNCE_COUNT {
+            ctrl.update(false);
+        }
+        for _ in 0..=DEBOUNCE_COUNT {
+            ctrl.update(true);
+        }
+        assert_eq!(ctrl.bounces_rejected(), 0);
+    }
+
+    #[test]
+    fn test_rapid_bouncing_counted() {
+        let mut ctrl = ButtonController::new();
+        for _ in 0..10 {
+            ctrl.update(false);
+            ctrl.update(true);
+        }
+        assert_eq!(ctrl.bounces_rejected(), 10);
+    }
+
     // ==================== Edge Case Tests ====================
 
     #[test]
@@ -239,6 +992,320 @@ mod tests {
         }
     }
 
+    // ==================== Event Tests ====================
+
+    #[test]
+    fn test_update_reports_pressed_once() {
+        let mut ctrl = ButtonController::new();
+        for _ in 0..DEBOUNCE_COUNT {
+            assert_eq!(ctrl.update(false), None);
+        }
+        assert_eq!(ctrl.update(false), Some(ButtonEvent::Pressed));
+        assert_eq!(ctrl.update(false), None);
+    }
+
+    #[test]
+    fn test_update_reports_released() {
+        let mut ctrl = ButtonController::new();
+        for _ in 0..=DEBOUNCE_COUNT {
+            ctrl.update(false);
+        }
+        for _ in 0..DEBOUNCE_COUNT {
+            assert_eq!(ctrl.update(true), None);
+        }
+        assert_eq!(ctrl.update(true), Some(ButtonEvent::Released));
+    }
+
+    #[test]
+    fn test_no_event_when_idle() {
+        let mut ctrl = ButtonController::new();
+        for _ in 0..10 {
+            assert_eq!(ctrl.update(true), None);
+        }
+    }
+
+    // ==================== Resume Tests ====================
+
+    #[test]
+    fn test_resume_pressed_reports_press() {
+        let mut ctrl = ButtonController::new();
+        assert_eq!(ctrl.resume_pressed(), Some(ButtonEvent::Pressed));
+        assert!(ctrl.is_pressed());
+    }
+
+    #[test]
+    fn test_resume_pressed_holds_without_refiltering() {
+        let mut ctrl = ButtonController::new();
+        ctrl.resume_pressed();
+        assert_eq!(ctrl.update(false), None);
+        assert!(ctrl.is_pressed());
+    }
+
+    #[test]
+    fn test_resume_pressed_when_already_pressed() {
+        let mut ctrl = ButtonController::new();
+        for _ in 0..=DEBOUNCE_COUNT {
+            ctrl.update(false);
+        }
+        assert_eq!(ctrl.resume_pressed(), None);
+    }
+
+    #[test]
+    fn test_release_after_resume() {
+        let mut ctrl = ButtonController::new();
+        ctrl.resume_pressed();
+        for _ in 0..DEBOUNCE_COUNT {
+            assert_eq!(ctrl.update(true), None);
+        }
+        assert_eq!(ctrl.update(true), Some(ButtonEvent::Released));
+    }
+
+    // ==================== TimedButtonController Tests ====================
+
+    /// Deterministic bouncy input: runs of random length and level.
+    fn bouncy_levels(seed: u32, len: usize) -> Vec<bool> {
+        let mut state = seed;
+        let mut level = true;
+        let mut levels = Vec::with_capacity(len);
+        while levels.len() < len {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let run = 1 + (state >> 16) as usize % 12;
+            levels.extend(core::iter::repeat_n(level, run));
+            level = !level;
+        }
+        levels.truncate(len);
+        levels
+    }
+
+    #[test]
+    fn test_timed_default_window() {
+        let ctrl = TimedButtonController::new();
+        assert_eq!(ctrl.stable_us(), DEBOUNCE_STABLE_US);
+        assert_eq!(ctrl, TimedButtonController::default());
+        assert!(!ctrl.is_pressed());
+        assert!(!ctrl.is_settled());
+    }
+
+    #[test]
+    fn test_timed_matches_count_at_fixed_rates() {
+        for &(count, period_us) in &[(1u32, 1_000u64), (3, 2_000), (5, 5_000), (8, 250)] {
+            for seed in 0..20 {
+                let mut counted = ButtonController::with_debounce_count(count);
+                let mut timed = TimedButtonController::with_stable_us(count as u64 * period_us);
+                for (i, &level) in bouncy_levels(seed, 400).iter().enumerate() {
+                    let now = 7 + i as u64 * period_us;
+                    assert_eq!(counted.update(level), timed.update(level, now));
+                    assert_eq!(counted.is_pressed(), timed.is_pressed());
+                    assert_eq!(counted.bounces_rejected(), timed.bounces_rejected());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_timed_settled_matches_count_after_first_change() {
+        let mut counted = ButtonController::with_debounce_count(4);
+        let mut timed = TimedButtonController::with_stable_us(4_000);
+        let levels = [
+            false, false, false, false, false, true, true, true, true, true,
+        ];
+        for (i, &level) in levels.iter().enumerate() {
+            counted.update(level);
+            timed.update(level, i as u64 * 1_000);
+            assert_eq!(counted.is_settled(), timed.is_settled());
+        }
+    }
+
+    #[test]
+    fn test_timed_window_independent_of_rate() {
+        for &period_us in &[500u64, 1_000, 5_000, 12_500] {
+            let mut ctrl = TimedButtonController::with_stable_us(25_000);
+            let mut pressed_at = None;
+            let mut now = 0;
+            while pressed_at.is_none() {
+                if ctrl.update(false, now) == Some(ButtonEvent::Pressed) {
+                    pressed_at = Some(now);
+                }
+                now += period_us;
+            }
+            assert_eq!(pressed_at, Some(25_000));
+        }
+    }
+
+    #[test]
+    fn test_timed_jittery_sampling() {
+        let mut ctrl = TimedButtonController::with_stable_us(10_000);
+        let times = [0u64, 1_300, 4_100, 4_900, 9_999, 10_001, 14_000];
+        let events: Vec<_> = times.iter().map(|&t| ctrl.update(false, t)).collect();
+        assert_eq!(events[..5], [None; 5]);
+        assert_eq!(events[5], Some(ButtonEvent::Pressed));
+        assert_eq!(events[6], None);
+    }
+
+    #[test]
+    fn test_timed_sparse_sampling_still_confirms() {
+        let mut ctrl = TimedButtonController::with_stable_us(25_000);
+        assert_eq!(ctrl.update(false, 0), None);
+        assert_eq!(ctrl.update(false, 80_000), Some(ButtonEvent::Pressed));
+    }
+
+    #[test]
+    fn test_timed_fast_glitch_rejected() {
+        let mut ctrl = TimedButtonController::with_stable_us(5_000);
+        for t in (0..4_900).step_by(100) {
+            assert_eq!(ctrl.update(false, t), None);
+        }
+        assert_eq!(ctrl.update(true, 4_900), None);
+        assert!(!ctrl.is_pressed());
+        assert_eq!(ctrl.bounces_rejected(), 1);
+    }
+
+    #[test]
+    fn test_timed_backwards_time_does_not_confirm() {
+        let mut ctrl = TimedButtonController::with_stable_us(1_000);
+        ctrl.update(false, 10_000);
+        assert_eq!(ctrl.update(false, 5_000), None);
+        assert_eq!(ctrl.update(false, 11_000), Some(ButtonEvent::Pressed));
+    }
+
+    #[test]
+    fn test_timed_resume_pressed() {
+        let mut ctrl = TimedButtonController::with_stable_us(5_000);
+        assert_eq!(ctrl.resume_pressed(100), Some(ButtonEvent::Pressed));
+        assert!(ctrl.is_settled());
+        assert_eq!(ctrl.resume_pressed(200), None);
+        assert_eq!(ctrl.update(true, 1_000), None);
+        assert_eq!(ctrl.update(true, 6_000), Some(ButtonEvent::Released));
+    }
+
+    // ==================== Eager Strategy Tests ====================
+
+    #[test]
+    fn test_default_strategy_conservative() {
+        assert_eq!(
+            ButtonController::new().strategy(),
+            DebounceStrategy::Conservative
+        );
+        assert_eq!(
+            TimedButtonController::new().strategy(),
+            DebounceStrategy::Conservative
+        );
+    }
+
+    #[test]
+    fn test_eager_reports_first_sample() {
+        let mut eager = ButtonController::with_strategy(DebounceStrategy::Eager, 5);
+        let mut conservative = ButtonController::with_debounce_count(5);
+        assert!(eager.is_settled());
+        assert_eq!(eager.update(false), Some(ButtonEvent::Pressed));
+        assert_eq!(conservative.update(false), None);
+        for _ in 0..4 {
+            assert_eq!(conservative.update(false), None);
+        }
+        assert_eq!(conservative.update(false), Some(ButtonEvent::Pressed));
+    }
+
+    #[test]
+    fn test_eager_ignores_bounce_during_lockout() {
+        let mut eager = ButtonController::with_strategy(DebounceStrategy::Eager, 5);
+        let mut conservative = ButtonController::with_debounce_count(5);
+        let levels = [
+            false, true, false, true, false, false, false, false, false, false,
+        ];
+        let eager_events: Vec<_> = levels.iter().filter_map(|&l| eager.update(l)).collect();
+        let conservative_events: Vec<_> = levels
+            .iter()
+            .filter_map(|&l| conservative.update(l))
+            .collect();
+        assert_eq!(eager_events, [ButtonEvent::Pressed]);
+        assert_eq!(conservative_events, [ButtonEvent::Pressed]);
+        assert_eq!(eager.bounces_rejected(), 4);
+        assert!(eager.is_settled());
+    }
+
+    #[test]
+    fn test_eager_reports_glitch_conservative_rejects() {
+        let mut eager = ButtonController::with_strategy(DebounceStrategy::Eager, 3);
+        let mut conservative = ButtonController::with_debounce_count(3);
+        let levels = [true, false, true, true, true, true, true];
+        let eager_events: Vec<_> = levels.iter().filter_map(|&l| eager.update(l)).collect();
+        let conservative_events: Vec<_> = levels
+            .iter()
+            .filter_map(|&l| conservative.update(l))
+            .collect();
+        assert_eq!(eager_events, [ButtonEvent::Pressed, ButtonEvent::Released]);
+        assert!(conservative_events.is_empty());
+        assert!(!eager.is_pressed());
+        assert!(!conservative.is_pressed());
+    }
+
+    #[test]
+    fn test_eager_release_after_lockout() {
+        let mut ctrl = ButtonController::with_strategy(DebounceStrategy::Eager, 3);
+        assert_eq!(ctrl.update(false), Some(ButtonEvent::Pressed));
+        for _ in 0..3 {
+            assert_eq!(ctrl.update(true), None);
+        }
+        assert!(ctrl.is_pressed());
+        assert_eq!(ctrl.update(true), Some(ButtonEvent::Released));
+    }
+
+    #[test]
+    fn test_eager_and_conservative_agree_when_stable() {
+        for seed in 0..50 {
+            let mut eager = ButtonController::with_strategy(DebounceStrategy::Eager, 4);
+            let mut conservative = ButtonController::with_debounce_count(4);
+            let mut levels = bouncy_levels(seed, 200);
+            let last = levels[levels.len() - 1];
+            levels.extend([last; 10]);
+            for level in levels {
+                eager.update(level);
+                conservative.update(level);
+            }
+            assert_eq!(eager.is_pressed(), conservative.is_pressed());
+            assert!(eager.is_settled() && conservative.is_settled());
+        }
+    }
+
+    #[test]
+    fn test_eager_resume_starts_lockout() {
+        let mut ctrl = ButtonController::with_strategy(DebounceStrategy::Eager, 2);
+        assert_eq!(ctrl.resume_pressed(), Some(ButtonEvent::Pressed));
+        assert_eq!(ctrl.update(true), None);
+        assert_eq!(ctrl.update(true), None);
+        assert_eq!(ctrl.update(true), Some(ButtonEvent::Released));
+    }
+
+    #[test]
+    fn test_timed_eager_matches_count_eager() {
+        for &(count, period_us) in &[(1u32, 1_000u64), (3, 2_000), (5, 5_000)] {
+            for seed in 0..20 {
+                let mut counted = ButtonController::with_strategy(DebounceStrategy::Eager, count);
+                let mut timed = TimedButtonController::with_strategy(
+                    DebounceStrategy::Eager,
+                    count as u64 * period_us,
+                );
+                for (i, &level) in bouncy_levels(seed, 400).iter().enumerate() {
+                    let now = 3 + i as u64 * period_us;
+                    assert_eq!(counted.update(level), timed.update(level, now));
+                    assert_eq!(counted.bounces_rejected(), timed.bounces_rejected());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_timed_eager_lockout_by_time() {
+        let mut ctrl = TimedButtonController::with_strategy(DebounceStrategy::Eager, 10_000);
+        assert_eq!(ctrl.update(false, 0), Some(ButtonEvent::Pressed));
+        assert_eq!(ctrl.update(true, 9_000), None);
+        assert_eq!(ctrl.update(true, 10_000), None);
+        assert_eq!(ctrl.update(true, 10_001), Some(ButtonEvent::Released));
+        assert_eq!(ctrl.resume_pressed(20_000), Some(ButtonEvent::Pressed));
+        assert_eq!(ctrl.update(true, 25_000), None);
+        assert_eq!(ctrl.update(true, 30_500), Some(ButtonEvent::Released));
+    }
+
     // ==================== Trait Implementation Tests ====================
 
     #[test]
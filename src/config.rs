@@ -38,7 +38,30 @@
 //!
 //! AUTHOR: Kevin Thomas
 //! CREATION DATE: November 28, 2025
-//! UPDATE DATE: December 5, 2025
+//! UPDATE DATE: October 18, 2026
+
+use crate::board::BOARD;
+use crate::button::DebounceStrategy;
+
+/// Values from `button.toml`, generated by `build.rs`.
+///
+/// # Details
+/// Each value is `None` unless the file sets it; see `configured!`.
+/// The keymap is always present and defaults to `KEYMAP_4X4`.
+#[allow(dead_code)]
+pub(crate) mod generated {
+    include!(concat!(env!("OUT_DIR"), "/button_config.rs"));
+}
+
+/// Returns a `button.toml` value, or `default` if the file omits it.
+macro_rules! configured {
+    ($name:ident, $default:expr) => {
+        match generated::$name {
+            Some(value) => value,
+            None => $default,
+        }
+    };
+}
 
 /// Default debounce delay in milliseconds.
 ///
@@ -47,9 +70,9 @@
 /// Used for software debouncing filter.
 ///
 /// # Value
-/// 5 milliseconds
+/// 5 milliseconds (default; `[debounce] delay_ms` in `button.toml`)
 #[allow(dead_code)]
-pub const DEBOUNCE_DELAY_MS: u64 = 5;
+pub const DEBOUNCE_DELAY_MS: u64 = configured!(DEBOUNCE_DELAY_MS, 5);
 
 /// Default debounce sample count threshold.
 ///
@@ -58,31 +81,594 @@ pub const DEBOUNCE_DELAY_MS: u64 = 5;
 /// Higher values provide better noise immunity but slower response.
 ///
 /// # Value
-/// 5 samples
+/// 5 samples (default; `[debounce] count` in `button.toml`)
+#[allow(dead_code)]
+pub const DEBOUNCE_COUNT: u32 = configured!(DEBOUNCE_COUNT, 5);
+
+/// Debounce strategy used by the firmware.
+///
+/// # Details
+/// `Eager` reports edges without delay and ignores the input for the
+/// debounce window afterwards; see `DebounceStrategy`.
+///
+/// # Value
+/// `Conservative` (default; `[debounce] strategy` in `button.toml`)
+#[allow(dead_code)]
+pub const DEBOUNCE_STRATEGY: DebounceStrategy =
+    configured!(DEBOUNCE_STRATEGY, DebounceStrategy::Conservative);
+
+/// Default debounce stability window in microseconds.
+///
+/// # Details
+/// Time the raw input must hold a new level before the time-based
+/// controller reports it. Matches the count-based window at the
+/// default sampling interval.
+///
+/// # Value
+/// `DEBOUNCE_COUNT * DEBOUNCE_DELAY_MS` milliseconds (25 000 us default)
+#[allow(dead_code)]
+pub const DEBOUNCE_STABLE_US: u64 = DEBOUNCE_COUNT as u64 * DEBOUNCE_DELAY_MS * 1000;
+
+/// Maximum allowed debounce sample count.
+///
+/// # Details
+/// Upper bound applied when validating persisted settings.
+///
+/// # Value
+/// 100 samples
+#[allow(dead_code)]
+pub const MAX_DEBOUNCE_COUNT: u32 = 100;
+
+/// Long-press duration threshold in milliseconds.
+///
+/// # Details
+/// Presses held at least this long are counted as long-presses.
+///
+/// # Value
+/// 1000 milliseconds (default; `[debounce] long_press_ms` in `button.toml`)
+#[allow(dead_code)]
+pub const LONG_PRESS_MS: u64 = configured!(LONG_PRESS_MS, 1000);
+
+/// Maximum press duration before reporting a stuck button.
+///
+/// # Details
+/// A press held longer than this is treated as a shorted switch.
+///
+/// # Value
+/// 30000 milliseconds (30 seconds)
+#[allow(dead_code)]
+pub const MAX_HOLD_MS: u64 = 30_000;
+
+/// Chatter detection window in milliseconds.
+///
+/// # Details
+/// Raw edges are counted over windows of this length.
+///
+/// # Value
+/// 1000 milliseconds
+#[allow(dead_code)]
+pub const CHATTER_WINDOW_MS: u64 = 1000;
+
+/// Maximum raw edges per chatter window.
+///
+/// # Details
+/// More edges than this within one window is reported as chatter.
+///
+/// # Value
+/// 40 edges
+#[allow(dead_code)]
+pub const CHATTER_MAX_EDGES: u32 = 40;
+
+/// Quiet time required to recover from chatter in milliseconds.
+///
+/// # Details
+/// Chatter clears once no raw edge is seen for this long.
+///
+/// # Value
+/// 2000 milliseconds
+#[allow(dead_code)]
+pub const FAULT_RECOVERY_MS: u64 = 2000;
+
+/// Fault indication blink time in milliseconds.
+///
+/// # Details
+/// On and off time of each LED pulse in fault patterns.
+///
+/// # Value
+/// 100 milliseconds (default; `[led] fault_blink_ms` in `button.toml`)
+#[allow(dead_code)]
+pub const FAULT_BLINK_MS: u32 = configured!(FAULT_BLINK_MS, 100);
+
+/// Fault indication pause in milliseconds.
+///
+/// # Details
+/// Gap between pulse groups in fault patterns.
+///
+/// # Value
+/// 600 milliseconds (default; `[led] fault_pause_ms` in `button.toml`)
+#[allow(dead_code)]
+pub const FAULT_PAUSE_MS: u32 = configured!(FAULT_PAUSE_MS, 600);
+
+/// Watchdog timeout in milliseconds.
+///
+/// # Details
+/// Must exceed the feed interval plus the longest blocking flash erase.
+///
+/// # Value
+/// 2000 milliseconds
+#[allow(dead_code)]
+pub const WATCHDOG_TIMEOUT_MS: u64 = 2000;
+
+/// Watchdog feed interval in milliseconds.
+///
+/// # Details
+/// Liveness is checked and the watchdog fed once per interval.
+///
+/// # Value
+/// 500 milliseconds
+#[allow(dead_code)]
+pub const WATCHDOG_FEED_MS: u64 = 500;
+
+/// LED task update interval in milliseconds.
+///
+/// # Details
+/// Resolution at which LED patterns are played.
+///
+/// # Value
+/// 10 milliseconds (default; `[led] tick_ms` in `button.toml`)
+#[allow(dead_code)]
+pub const LED_TICK_MS: u64 = configured!(LED_TICK_MS, 10);
+
+/// Duration of the reset cause indication at boot in milliseconds.
+///
+/// # Details
+/// LED shows the watchdog reset pattern for this long before normal operation.
+///
+/// # Value
+/// 3000 milliseconds
+#[allow(dead_code)]
+pub const RESET_INDICATION_MS: u64 = 3000;
+
+/// Idle time before entering low-power sleep in milliseconds.
+///
+/// # Details
+/// After this long without button activity the LED is turned off and
+/// the firmware sleeps until the next button press.
+///
+/// # Value
+/// 60000 milliseconds (1 minute)
+#[allow(dead_code)]
+pub const IDLE_TIMEOUT_MS: u64 = 60_000;
+
+/// Hold time that arms the BOOTSEL reboot gesture in milliseconds.
+///
+/// # Details
+/// Holding the button this long at power-up, then releasing it,
+/// reboots into the ROM USB bootloader.
+///
+/// # Value
+/// 10000 milliseconds (10 seconds)
+#[allow(dead_code)]
+pub const BOOTSEL_HOLD_MS: u64 = 10_000;
+
+/// Window after boot in which the BOOTSEL press must register.
+///
+/// # Details
+/// A press first debounced later than this is treated as normal use.
+///
+/// # Value
+/// 250 milliseconds
+#[allow(dead_code)]
+pub const BOOTSEL_BOOT_WINDOW_MS: u64 = 250;
+
+/// Stable samples required on each rotary encoder channel.
+///
+/// # Details
+/// Encoder channels are sampled much faster than the button and the
+/// quadrature table already rejects single-channel bounce.
+///
+/// # Value
+/// 1 sample
+#[allow(dead_code)]
+pub const ENCODER_DEBOUNCE_COUNT: u32 = 1;
+
+/// Step interval below which encoder acceleration applies.
+///
+/// # Details
+/// Steps closer together than this are multiplied by up to
+/// `ENCODER_ACCEL_MAX`, in proportion to the turning speed.
+///
+/// # Value
+/// 40 milliseconds
+#[allow(dead_code)]
+pub const ENCODER_ACCEL_MS: u64 = 40;
+
+/// Maximum encoder acceleration multiplier.
+///
+/// # Value
+/// 8x
+#[allow(dead_code)]
+pub const ENCODER_ACCEL_MAX: u32 = 8;
+
+/// Maximum keypad keys reported as held at once.
+///
+/// # Details
+/// Presses beyond this limit are ignored until a key is released.
+///
+/// # Value
+/// 4 keys
+#[allow(dead_code)]
+pub const KEYPAD_MAX_KEYS: usize = 4;
+
+/// Keypad rows in `KEYMAP`.
+///
+/// # Value
+/// 4 rows (default; from `[keypad] keymap` in `button.toml`)
+#[allow(dead_code)]
+pub const KEYMAP_ROWS: usize = generated::KEYMAP_ROWS;
+
+/// Keypad columns in `KEYMAP`.
+///
+/// # Value
+/// 4 columns (default; from `[keypad] keymap` in `button.toml`)
+#[allow(dead_code)]
+pub const KEYMAP_COLS: usize = generated::KEYMAP_COLS;
+
+/// Product keypad key mapping.
+///
+/// # Details
+/// Character for each row and column, for use with `KeyEvent::key`.
+///
+/// # Value
+/// `KEYMAP_4X4` (default; `[keypad] keymap` in `button.toml`)
+#[allow(dead_code)]
+pub const KEYMAP: [[char; KEYMAP_COLS]; KEYMAP_ROWS] = generated::KEYMAP;
+
+/// Nominal 12-bit ADC readings of a five-button resistor ladder.
+///
+/// # Details
+/// Readings for buttons 0 to 4 with a 10k pull-up to 3.3 V and
+/// 0, 1k8, 4k7, 10k and 22k to ground. Refine with calibration.
+///
+/// # Value
+/// 0, 624, 1309, 2048, 2816 counts
+#[allow(dead_code)]
+pub const LADDER_LEVELS: [u16; 5] = [0, 624, 1309, 2048, 2816];
+
+/// Nominal ADC reading with no ladder button pressed.
+///
+/// # Value
+/// 4095 counts (full scale)
+#[allow(dead_code)]
+pub const LADDER_IDLE_LEVEL: u16 = 4095;
+
+/// Hysteresis applied when moving between ladder windows.
+///
+/// # Details
+/// A reading must be this much closer to another level before the
+/// classification changes, so noise at a boundary does not flicker.
+///
+/// # Value
+/// 48 counts
+#[allow(dead_code)]
+pub const LADDER_HYSTERESIS: u16 = 48;
+
+/// Charge-time increase over baseline that counts as a touch.
+///
+/// # Details
+/// In raw measurement counts; depends on pad size and overlay.
+///
+/// # Value
+/// 50 counts
+#[allow(dead_code)]
+pub const TOUCH_THRESHOLD: u32 = 50;
+
+/// Hysteresis below the touch threshold before a release is seen.
+///
+/// # Value
+/// 15 counts
+#[allow(dead_code)]
+pub const TOUCH_HYSTERESIS: u32 = 15;
+
+/// Baseline filter shift for touch drift compensation.
+///
+/// # Details
+/// Each untouched sample moves the baseline by 1/2^shift of the
+/// difference, so slow temperature and humidity drift is absorbed.
+///
+/// # Value
+/// 6 (1/64 per sample)
+#[allow(dead_code)]
+pub const TOUCH_BASELINE_SHIFT: u8 = 6;
+
+/// Samples averaged for the initial touch baseline.
+///
+/// # Value
+/// 16 samples
+#[allow(dead_code)]
+pub const TOUCH_CALIBRATION_SAMPLES: u32 = 16;
+
+/// GPIO connected to the touch pad.
+///
+/// # Details
+/// Used with the `touch` feature. The pad needs an external pull-up
+/// resistor (around 1 MOhm) to 3V3 so its charge time is measurable.
+///
+/// # Value
+/// GPIO 22
+#[allow(dead_code)]
+pub const TOUCH_PIN: u8 = 22;
+
+/// Time the touch pad is driven low before each measurement.
+///
+/// # Value
+/// 10 microseconds
+#[allow(dead_code)]
+pub const TOUCH_DISCHARGE_US: u64 = 10;
+
+/// Upper bound on a touch charge-time count.
+///
+/// # Details
+/// Ends the measurement if the pad never charges, for example when
+/// the pull-up resistor is missing.
+///
+/// # Value
+/// 20 000 polling iterations
+#[allow(dead_code)]
+pub const TOUCH_MAX_COUNT: u32 = 20_000;
+
+/// First GPIO of the PIO-sampled input bank.
+///
+/// # Details
+/// Used with the `pio-debounce` feature; the bank is contiguous. The
+/// bank is fixed: `main.rs` takes `PIN_2` to `PIN_5` by name, and
+/// `board_pin!` fails the build if this constant no longer matches
+/// them, so moving the bank means editing that pin list too.
+///
+/// # Value
+/// GPIO 2
+#[allow(dead_code)]
+pub const PIO_INPUT_BASE_PIN: u8 = 2;
+
+/// Number of GPIOs in the PIO-sampled input bank.
+///
+/// # Details
+/// Fixed with the pin list in `main.rs`, whose array length must match.
+///
+/// # Value
+/// 4 pins (GPIO 2 to 5)
+#[allow(dead_code)]
+pub const PIO_INPUT_COUNT: u8 = 4;
+
+/// Checks whether a GPIO belongs to the PIO-sampled input bank.
+///
+/// # Arguments
+/// * `pin` - GPIO number
+///
+/// # Returns
+/// * `bool` - True if `pin` is in the bank
+#[allow(dead_code)]
+pub const fn in_pio_input_bank(pin: u8) -> bool {
+    pin >= PIO_INPUT_BASE_PIN && pin < PIO_INPUT_BASE_PIN + PIO_INPUT_COUNT
+}
+
+/// Nominal RP2350 system clock in hertz.
+///
+/// # Details
+/// Embassy's default clock configuration; used to check the PIO clock
+/// divider at compile time. The firmware still reads the actual clock
+/// at runtime.
+///
+/// # Value
+/// 150 MHz
+#[allow(dead_code)]
+pub const SYS_CLOCK_HZ: u32 = 150_000_000;
+
+/// PIO input bank state machine clock in hertz.
+///
+/// # Details
+/// The sampling program counts the debounce interval in cycles of
+/// this clock, so any `DEBOUNCE_DELAY_MS` is paced exactly.
+///
+/// # Value
+/// 1 MHz
+#[allow(dead_code)]
+pub const PIO_SAMPLE_CLOCK_HZ: u32 = 1_000_000;
+
+/// PIO cycles per input bank sample.
+///
+/// # Details
+/// One debounce interval at `PIO_SAMPLE_CLOCK_HZ`.
+///
+/// # Value
+/// `DEBOUNCE_DELAY_MS` milliseconds of PIO cycles (5000 default)
+#[allow(dead_code)]
+pub const PIO_SAMPLE_CYCLES: u64 = PIO_SAMPLE_CLOCK_HZ as u64 / 1000 * DEBOUNCE_DELAY_MS;
+
+/// PIO cycles per sample spent outside the delay loop.
+///
+/// # Details
+/// The sampling program spends one cycle on `in`, one on `mov x, y`
+/// and one on the final `jmp x--`; the loop adds one cycle per count.
+///
+/// # Value
+/// 3 cycles
 #[allow(dead_code)]
-pub const DEBOUNCE_COUNT: u32 = 5;
+pub const PIO_SAMPLE_OVERHEAD_CYCLES: u64 = 3;
+
+/// Delay loop count loaded into the sampling program.
+///
+/// # Value
+/// `PIO_SAMPLE_CYCLES - PIO_SAMPLE_OVERHEAD_CYCLES`
+#[allow(dead_code)]
+pub const PIO_SAMPLE_LOOPS: u32 =
+    PIO_SAMPLE_CYCLES.saturating_sub(PIO_SAMPLE_OVERHEAD_CYCLES) as u32;
+
+/// Computes the PIO clock divider for the sampling clock.
+///
+/// # Details
+/// The divider has a 16-bit integer and an 8-bit fractional part.
+///
+/// # Arguments
+/// * `sys_clock_hz` - System clock in hertz
+///
+/// # Returns
+/// * `Option<u32>` - Divider as 16.8 fixed-point bits, or None if the
+///   system clock cannot be divided down to `PIO_SAMPLE_CLOCK_HZ`
+#[allow(dead_code)]
+pub const fn pio_clock_divider_bits(sys_clock_hz: u32) -> Option<u32> {
+    let bits = (sys_clock_hz as u64 * 256) / PIO_SAMPLE_CLOCK_HZ as u64;
+    if bits < 256 || bits > (u16::MAX as u64) << 8 {
+        return None;
+    }
+    Some(bits as u32)
+}
+
+#[cfg(feature = "pio-debounce")]
+const _: () = assert!(
+    PIO_SAMPLE_CYCLES > PIO_SAMPLE_OVERHEAD_CYCLES
+        && PIO_SAMPLE_CYCLES - PIO_SAMPLE_OVERHEAD_CYCLES <= u32::MAX as u64,
+    "DEBOUNCE_DELAY_MS is out of range for the PIO sampling loop"
+);
+
+#[cfg(feature = "pio-debounce")]
+const _: () = assert!(
+    pio_clock_divider_bits(SYS_CLOCK_HZ).is_some(),
+    "PIO_SAMPLE_CLOCK_HZ is out of the PIO clock divider range"
+);
+
+#[cfg(feature = "pio-debounce")]
+const _: () = assert!(
+    PIO_INPUT_COUNT <= 32 && PIO_INPUT_BASE_PIN + PIO_INPUT_COUNT <= crate::board::GPIO_COUNT,
+    "PIO input bank does not fit in the GPIO range"
+);
+
+#[cfg(feature = "pio-debounce")]
+const _: () = assert!(
+    !in_pio_input_bank(BUTTON_PIN) && !in_pio_input_bank(LED_PIN),
+    "PIO input bank overlaps the button or LED pin"
+);
+
+/// Width of one latency histogram bucket in microseconds.
+///
+/// # Value
+/// 5000 microseconds (one sample interval)
+#[allow(dead_code)]
+pub const LATENCY_BUCKET_US: u64 = 5000;
+
+/// Number of latency histogram buckets.
+///
+/// # Details
+/// The last bucket also collects every longer latency.
+///
+/// # Value
+/// 16 buckets
+#[allow(dead_code)]
+pub const LATENCY_BUCKETS: usize = 16;
+
+const _: () = assert!(
+    DEBOUNCE_DELAY_MS * 1000 * (DEBOUNCE_COUNT as u64 + 1)
+        < LATENCY_BUCKET_US * LATENCY_BUCKETS as u64,
+    "debounce window does not fit the latency histogram; lower DEBOUNCE_DELAY_MS or DEBOUNCE_COUNT"
+);
+
+/// Initial Morse speed in words per minute.
+///
+/// # Details
+/// One dit lasts 1200 / WPM milliseconds. The input decoder adapts
+/// to the sender from this starting point.
+///
+/// # Value
+/// 12 WPM (100 ms dit) (default; `[led] morse_wpm` in `button.toml`)
+#[allow(dead_code)]
+pub const MORSE_WPM: u32 = configured!(MORSE_WPM, 12);
+
+const _: () = assert!(
+    1200 / MORSE_WPM as u64 > DEBOUNCE_DELAY_MS * (DEBOUNCE_COUNT as u64 + 1),
+    "Morse dit is not longer than the debounce window; lower MORSE_WPM or the debounce timing"
+);
+
+/// Maximum length of a Morse LED message.
+///
+/// # Details
+/// Longer text is truncated when the pattern is built.
+///
+/// # Value
+/// 32 characters
+#[allow(dead_code)]
+pub const MORSE_TEXT_CAPACITY: usize = 32;
+
+/// Maximum pause between presses of an unlock sequence.
+///
+/// # Details
+/// A partial sequence is discarded after this long without a press.
+///
+/// # Value
+/// 3000 milliseconds
+#[allow(dead_code)]
+pub const SEQUENCE_TIMEOUT_MS: u64 = 3000;
+
+const _: () = assert!(
+    LONG_PRESS_MS < SEQUENCE_TIMEOUT_MS,
+    "LONG_PRESS_MS must stay below SEQUENCE_TIMEOUT_MS"
+);
+
+/// Wrong sequences allowed before lockout.
+///
+/// # Value
+/// 3 attempts
+#[allow(dead_code)]
+pub const SEQUENCE_MAX_FAILURES: u32 = 3;
+
+/// Lockout duration after too many wrong sequences.
+///
+/// # Details
+/// Presses are ignored until the lockout expires.
+///
+/// # Value
+/// 30000 milliseconds (30 seconds)
+#[allow(dead_code)]
+pub const SEQUENCE_LOCKOUT_MS: u64 = 30_000;
+
+/// Capacity of the input event queue between cores.
+///
+/// # Details
+/// Must be a power of two. Holds events while core 0 is busy.
+///
+/// # Value
+/// 16 events
+#[allow(dead_code)]
+pub const INPUT_QUEUE_LEN: usize = 16;
+
+/// Stack size for the core 1 input executor.
+///
+/// # Value
+/// 4096 bytes
+#[allow(dead_code)]
+pub const CORE1_STACK_BYTES: usize = 4096;
 
 /// Button GPIO pin number.
 ///
 /// # Details
-/// GPIO pin connected to the button.
-/// Button is active-low (tied to GND when pressed).
+/// GPIO pin connected to the button, from the selected board profile.
+/// On the Pico 2 profile the button is active-low (tied to GND when
+/// pressed).
 ///
 /// # Value
-/// GPIO 15
+/// GPIO 15 (Pico 2 profile)
 #[allow(dead_code)]
-pub const BUTTON_PIN: u8 = 15;
+pub const BUTTON_PIN: u8 = BOARD.button_pin;
 
 /// LED GPIO pin number.
 ///
 /// # Details
-/// GPIO pin connected to the LED.
+/// GPIO pin connected to the LED, from the selected board profile.
 /// LED turns on when button is pressed.
 ///
 /// # Value
-/// GPIO 16
+/// GPIO 16 (Pico 2 profile)
 #[allow(dead_code)]
-pub const LED_PIN: u8 = 16;
+pub const LED_PIN: u8 = BOARD.led_pin;
 
 /// Default LED blink delay in milliseconds.
 ///
@@ -94,6 +680,73 @@ pub const LED_PIN: u8 = 16;
 #[allow(dead_code)]
 pub const BLINK_DELAY_MS: u64 = 500;
 
+const _: () = assert!(
+    DEBOUNCE_DELAY_MS < BLINK_DELAY_MS,
+    "DEBOUNCE_DELAY_MS must stay below BLINK_DELAY_MS"
+);
+
+/// Total flash size in bytes.
+///
+/// # Details
+/// Must match the FLASH region length plus reserved regions in `memory.x`.
+///
+/// # Value
+/// 4 MiB
+#[allow(dead_code)]
+pub const FLASH_SIZE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Flash erase sector size in bytes.
+///
+/// # Details
+/// Smallest erasable unit of the QSPI flash.
+///
+/// # Value
+/// 4096 bytes
+#[allow(dead_code)]
+pub const FLASH_SECTOR_SIZE: u32 = 4096;
+
+/// Number of flash sectors reserved for settings.
+///
+/// # Details
+/// Records rotate through these sectors for wear levelling.
+/// Must match the SETTINGS region length in `memory.x`.
+///
+/// # Value
+/// 4 sectors (16 KiB)
+#[allow(dead_code)]
+pub const SETTINGS_SECTOR_COUNT: u32 = 4;
+
+/// Flash offset of the settings region.
+///
+/// # Details
+/// Offset from the start of flash of the SETTINGS region in `memory.x`.
+///
+/// # Value
+/// Last 16 KiB of flash
+#[allow(dead_code)]
+pub const SETTINGS_FLASH_OFFSET: u32 =
+    FLASH_SIZE_BYTES as u32 - SETTINGS_SECTOR_COUNT * FLASH_SECTOR_SIZE;
+
+/// Settings record slot size in bytes.
+///
+/// # Details
+/// One flash page per record so every write is page-aligned.
+///
+/// # Value
+/// 256 bytes
+#[allow(dead_code)]
+pub const SETTINGS_SLOT_SIZE: u32 = 256;
+
+/// Minimum interval between settings saves in milliseconds.
+///
+/// # Details
+/// Limits flash wear from persisting press counters.
+///
+/// # Value
+/// 60000 milliseconds (1 minute)
+#[allow(dead_code)]
+pub const SETTINGS_SAVE_INTERVAL_MS: u64 = 60_000;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,7 +755,9 @@ mod tests {
 
     #[test]
     fn test_debounce_delay_default() {
-        assert_eq!(DEBOUNCE_DELAY_MS, 5);
+        if generated::DEBOUNCE_DELAY_MS.is_none() {
+            assert_eq!(DEBOUNCE_DELAY_MS, 5);
+        }
     }
 
     #[test]
@@ -110,14 +765,182 @@ mod tests {
         assert!(DEBOUNCE_COUNT > 0);
     }
 
+    #[test]
+    fn test_long_press_longer_than_debounce() {
+        assert!(LONG_PRESS_MS > DEBOUNCE_DELAY_MS * DEBOUNCE_COUNT as u64);
+    }
+
+    #[test]
+    fn test_max_hold_longer_than_long_press() {
+        assert!(MAX_HOLD_MS > LONG_PRESS_MS);
+    }
+
+    #[test]
+    fn test_chatter_limits_positive() {
+        assert!(CHATTER_WINDOW_MS > 0);
+        assert!(CHATTER_MAX_EDGES > 0);
+        assert!(FAULT_RECOVERY_MS > 0);
+    }
+
+    #[test]
+    fn test_watchdog_feed_within_timeout() {
+        assert!(WATCHDOG_FEED_MS * 2 <= WATCHDOG_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn test_led_tick_finer_than_fault_blink() {
+        assert!(LED_TICK_MS < FAULT_BLINK_MS as u64);
+    }
+
+    #[test]
+    fn test_idle_timeout_longer_than_long_press() {
+        assert!(IDLE_TIMEOUT_MS > LONG_PRESS_MS);
+    }
+
+    #[test]
+    fn test_bootsel_hold_longer_than_long_press() {
+        assert!(BOOTSEL_HOLD_MS > LONG_PRESS_MS);
+        assert!(BOOTSEL_HOLD_MS < MAX_HOLD_MS);
+    }
+
+    #[test]
+    fn test_bootsel_window_covers_debounce() {
+        assert!(BOOTSEL_BOOT_WINDOW_MS > DEBOUNCE_DELAY_MS * DEBOUNCE_COUNT as u64);
+    }
+
+    #[test]
+    fn test_encoder_acceleration_valid() {
+        assert!(ENCODER_DEBOUNCE_COUNT >= 1);
+        assert!(ENCODER_ACCEL_MS > 0);
+        assert!(ENCODER_ACCEL_MAX >= 1);
+    }
+
+    #[test]
+    fn test_keypad_rollover_valid() {
+        assert!(KEYPAD_MAX_KEYS >= 2);
+    }
+
+    #[test]
+    fn test_ladder_levels_separated() {
+        for pair in LADDER_LEVELS.windows(2) {
+            assert!(pair[1] - pair[0] > 2 * LADDER_HYSTERESIS);
+        }
+        assert!(LADDER_IDLE_LEVEL - LADDER_LEVELS[4] > 2 * LADDER_HYSTERESIS);
+    }
+
+    #[test]
+    fn test_touch_hysteresis_below_threshold() {
+        assert!(TOUCH_HYSTERESIS < TOUCH_THRESHOLD);
+        assert!(TOUCH_BASELINE_SHIFT < 16);
+        assert!(TOUCH_CALIBRATION_SAMPLES > 0);
+    }
+
+    #[test]
+    fn test_touch_pin_free() {
+        assert!(TOUCH_PIN < crate::board::GPIO_COUNT);
+        assert_ne!(TOUCH_PIN, BUTTON_PIN);
+        assert_ne!(TOUCH_PIN, LED_PIN);
+        assert!(!in_pio_input_bank(TOUCH_PIN));
+        assert!(TOUCH_MAX_COUNT > TOUCH_THRESHOLD);
+    }
+
+    #[test]
+    fn test_pio_input_bank_avoids_button_and_led() {
+        assert!(PIO_INPUT_COUNT as u32 <= 32);
+        assert!(PIO_INPUT_BASE_PIN + PIO_INPUT_COUNT <= crate::board::GPIO_COUNT);
+        assert!(!in_pio_input_bank(BUTTON_PIN));
+        assert!(!in_pio_input_bank(LED_PIN));
+    }
+
+    #[test]
+    fn test_pio_sample_period_matches_debounce_delay() {
+        let cycles = PIO_SAMPLE_LOOPS as u64 + PIO_SAMPLE_OVERHEAD_CYCLES;
+        assert_eq!(
+            cycles * 1000,
+            DEBOUNCE_DELAY_MS * PIO_SAMPLE_CLOCK_HZ as u64
+        );
+    }
+
+    #[test]
+    fn test_pio_clock_divider_range() {
+        assert_eq!(pio_clock_divider_bits(SYS_CLOCK_HZ), Some(150 << 8));
+        assert_eq!(pio_clock_divider_bits(133_000_000), Some(133 << 8));
+        assert_eq!(pio_clock_divider_bits(PIO_SAMPLE_CLOCK_HZ), Some(1 << 8));
+        assert_eq!(pio_clock_divider_bits(PIO_SAMPLE_CLOCK_HZ - 1), None);
+    }
+
+    #[test]
+    fn test_in_pio_input_bank_bounds() {
+        assert!(in_pio_input_bank(PIO_INPUT_BASE_PIN));
+        assert!(in_pio_input_bank(PIO_INPUT_BASE_PIN + PIO_INPUT_COUNT - 1));
+        assert!(!in_pio_input_bank(PIO_INPUT_BASE_PIN + PIO_INPUT_COUNT));
+        assert!(PIO_INPUT_BASE_PIN == 0 || !in_pio_input_bank(PIO_INPUT_BASE_PIN - 1));
+    }
+
+    #[test]
+    fn test_latency_histogram_covers_debounce_window() {
+        let window_us = DEBOUNCE_DELAY_MS * 1000 * (DEBOUNCE_COUNT as u64 + 1);
+        assert!(LATENCY_BUCKET_US * LATENCY_BUCKETS as u64 > window_us);
+    }
+
+    #[test]
+    fn test_morse_dit_longer_than_debounce() {
+        let dit_ms = 1200 / MORSE_WPM as u64;
+        assert!(dit_ms > DEBOUNCE_DELAY_MS * (DEBOUNCE_COUNT as u64 + 1));
+    }
+
+    #[test]
+    fn test_sequence_limits_valid() {
+        assert!(SEQUENCE_TIMEOUT_MS > LONG_PRESS_MS);
+        assert!(SEQUENCE_MAX_FAILURES >= 1);
+        assert!(SEQUENCE_LOCKOUT_MS > SEQUENCE_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn test_input_queue_len_power_of_two() {
+        assert!(INPUT_QUEUE_LEN.is_power_of_two());
+        assert!(INPUT_QUEUE_LEN >= 2);
+        assert_eq!(CORE1_STACK_BYTES % 8, 0);
+    }
+
     #[test]
     fn test_button_pin_valid() {
-        assert_eq!(BUTTON_PIN, 15);
+        assert!(BUTTON_PIN < crate::board::GPIO_COUNT);
+        if BOARD == crate::board::PICO2 {
+            assert_eq!(BUTTON_PIN, 15);
+        }
     }
 
     #[test]
     fn test_led_pin_valid() {
-        assert_eq!(LED_PIN, 16);
+        if BOARD == crate::board::PICO2 {
+            assert_eq!(LED_PIN, 16);
+        }
+    }
+
+    #[test]
+    fn test_keymap_dimensions() {
+        assert_eq!(KEYMAP.len(), KEYMAP_ROWS);
+        assert!(KEYMAP.iter().all(|row| row.len() == KEYMAP_COLS));
+        assert!(KEYMAP_COLS <= 32);
+    }
+
+    #[test]
+    fn test_unset_values_use_defaults() {
+        if generated::LONG_PRESS_MS.is_none() {
+            assert_eq!(LONG_PRESS_MS, 1000);
+        }
+        if generated::MORSE_WPM.is_none() {
+            assert_eq!(MORSE_WPM, 12);
+        }
+    }
+
+    #[test]
+    fn test_board_pad_settings_reach_input_config() {
+        let config = BOARD.input_config().unwrap();
+        assert_eq!(config.pull, BOARD.button_pull);
+        assert_eq!(config.schmitt, BOARD.button_schmitt);
+        assert_eq!(config.bypass_sync, BOARD.button_bypass_sync);
     }
 
     #[test]
@@ -140,6 +963,24 @@ mod tests {
         assert!(DEBOUNCE_DELAY_MS < BLINK_DELAY_MS);
     }
 
+    #[test]
+    fn test_debounce_count_within_max() {
+        assert!(DEBOUNCE_COUNT <= MAX_DEBOUNCE_COUNT);
+    }
+
+    // ==================== Flash Layout Tests ====================
+
+    #[test]
+    fn test_settings_region_sector_aligned() {
+        assert_eq!(SETTINGS_FLASH_OFFSET % FLASH_SECTOR_SIZE, 0);
+    }
+
+    #[test]
+    fn test_settings_region_at_end_of_flash() {
+        let end = SETTINGS_FLASH_OFFSET + SETTINGS_SECTOR_COUNT * FLASH_SECTOR_SIZE;
+        assert_eq!(end as usize, FLASH_SIZE_BYTES);
+    }
+
     #[test]
     fn test_gpio_pins_in_valid_range() {
         assert!(BUTTON_PIN < 30);
@@ -0,0 +1,546 @@
+/*
+ * @file encoder.rs
+ * @brief Quadrature rotary encoder decoder with push button
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: encoder.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Rotary Encoder Driver.
+//!
+//! BRIEF:
+//! Table-driven quadrature decoder for mechanical rotary encoders with
+//! full, half and quarter step modes, velocity and acceleration.
+//! Channels and the push switch are debounced by `ButtonController`.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{ButtonController, ButtonEvent};
+use crate::config::{ENCODER_ACCEL_MAX, ENCODER_ACCEL_MS, ENCODER_DEBOUNCE_COUNT};
+
+/// Quadrature transition table indexed by `previous << 2 | current`.
+///
+/// # Details
+/// States are `A << 1 | B`. Valid single-channel transitions yield +1
+/// when A leads B and -1 when B leads A; no change and invalid
+/// double-channel transitions yield 0.
+const QUADRATURE_TABLE: [i8; 16] = [0, -1, 1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, 1, -1, 0];
+
+/// Quadrature state with both channels high (idle detent with pull-ups).
+const STATE_IDLE: u8 = 0b11;
+
+/// Encoder step resolution.
+///
+/// # Variants
+/// * `Full` - One step per full quadrature cycle (detent at `11`)
+/// * `Half` - One step per half cycle (detents at `00` and `11`)
+/// * `Quarter` - One step per transition
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum StepMode {
+    Full,
+    Half,
+    Quarter,
+}
+
+impl StepMode {
+    /// Returns quadrature transitions per step.
+    ///
+    /// # Returns
+    /// * `i8` - Transitions per step
+    #[allow(dead_code)]
+    pub fn transitions(&self) -> i8 {
+        match self {
+            StepMode::Full => 4,
+            StepMode::Half => 2,
+            StepMode::Quarter => 1,
+        }
+    }
+
+    /// Returns true if `state` is a mechanical rest position.
+    ///
+    /// # Arguments
+    /// * `state` - Quadrature state `A << 1 | B`
+    ///
+    /// # Returns
+    /// * `bool` - true at a detent
+    fn is_detent(&self, state: u8) -> bool {
+        match self {
+            StepMode::Full => state == STATE_IDLE,
+            StepMode::Half => state == STATE_IDLE || state == 0,
+            StepMode::Quarter => true,
+        }
+    }
+}
+
+/// Rotary encoder configuration.
+///
+/// # Fields
+/// * `mode` - Step resolution
+/// * `debounce_count` - Stable samples required per channel
+/// * `accel_ms` - Step interval below which acceleration applies
+/// * `accel_max` - Maximum acceleration multiplier
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct EncoderConfig {
+    pub mode: StepMode,
+    pub debounce_count: u32,
+    pub accel_ms: u64,
+    pub accel_max: u32,
+}
+
+impl Default for EncoderConfig {
+    /// Returns full-step configuration from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default encoder configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            mode: StepMode::Full,
+            debounce_count: ENCODER_DEBOUNCE_COUNT,
+            accel_ms: ENCODER_ACCEL_MS,
+            accel_max: ENCODER_ACCEL_MAX,
+        }
+    }
+}
+
+/// Quadrature rotary encoder decoder.
+///
+/// # Details
+/// Each channel is filtered by its own `ButtonController`, so a high
+/// level reads as released. Transitions are accumulated through the
+/// quadrature table and converted into steps at the configured
+/// resolution. Partial steps are discarded at detents, which
+/// resynchronises the decoder after contact bounce.
+///
+/// # Fields
+/// * `config` - Encoder configuration
+/// * `a` - Channel A debouncer
+/// * `b` - Channel B debouncer
+/// * `state` - Last debounced quadrature state
+/// * `accum` - Transitions accumulated toward the next step
+/// * `position` - Net steps without acceleration
+/// * `last_step_ms` - Timestamp of the last step
+/// * `last_interval_ms` - Interval between the last two steps
+/// * `last_direction` - Direction of the last step (+1 or -1)
+/// * `invalid` - Invalid double-channel transitions seen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct Encoder {
+    config: EncoderConfig,
+    a: ButtonController,
+    b: ButtonController,
+    state: u8,
+    accum: i8,
+    position: i32,
+    last_step_ms: Option<u64>,
+    last_interval_ms: u64,
+    last_direction: i32,
+    invalid: u32,
+}
+
+impl Encoder {
+    /// Creates encoder at the idle detent.
+    ///
+    /// # Arguments
+    /// * `config` - Encoder configuration
+    ///
+    /// # Returns
+    /// * `Self` - New Encoder instance
+    #[allow(dead_code)]
+    pub fn new(config: EncoderConfig) -> Self {
+        Self {
+            config,
+            a: ButtonController::with_debounce_count(config.debounce_count),
+            b: ButtonController::with_debounce_count(config.debounce_count),
+            state: STATE_IDLE,
+            accum: 0,
+            position: 0,
+            last_step_ms: None,
+            last_interval_ms: u64::MAX,
+            last_direction: 0,
+            invalid: 0,
+        }
+    }
+
+    /// Updates the decoder with new channel samples.
+    ///
+    /// # Arguments
+    /// * `a_high` - true if channel A is high
+    /// * `b_high` - true if channel B is high
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `i32` - Accelerated steps, positive clockwise (A leads B)
+    #[allow(dead_code)]
+    pub fn update(&mut self, a_high: bool, b_high: bool, now_ms: u64) -> i32 {
+        self.a.update(a_high);
+        self.b.update(b_high);
+        let state = ((!self.a.is_pressed() as u8) << 1) | !self.b.is_pressed() as u8;
+        if state == self.state {
+            return 0;
+        }
+        let delta = QUADRATURE_TABLE[((self.state << 2) | state) as usize];
+        self.state = state;
+        if delta == 0 {
+            self.invalid = self.invalid.saturating_add(1);
+            self.accum = 0;
+            return 0;
+        }
+        self.accum += delta;
+        let steps = self.config.mode.transitions();
+        let direction = if self.accum >= steps {
+            1
+        } else if self.accum <= -steps {
+            -1
+        } else {
+            if self.config.mode.is_detent(state) {
+                self.accum = 0;
+            }
+            return 0;
+        };
+        self.accum = 0;
+        self.position = self.position.wrapping_add(direction);
+        let multiplier = self.step_multiplier(now_ms);
+        self.last_direction = direction;
+        trace!("encoder: step {}, x{}", direction, multiplier);
+        direction * multiplier as i32
+    }
+
+    /// Returns net steps taken, without acceleration.
+    ///
+    /// # Returns
+    /// * `i32` - Position in steps
+    #[allow(dead_code)]
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Returns the current turning speed.
+    ///
+    /// # Details
+    /// Uses the interval between the last two steps, or the time since
+    /// the last step if longer, so the speed decays once turning stops.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `i32` - Steps per second, positive clockwise
+    #[allow(dead_code)]
+    pub fn velocity(&self, now_ms: u64) -> i32 {
+        let Some(last) = self.last_step_ms else {
+            return 0;
+        };
+        let interval = self.last_interval_ms.max(now_ms.saturating_sub(last));
+        self.last_direction * (1000 / interval.max(1)).min(i32::MAX as u64) as i32
+    }
+
+    /// Returns number of invalid transitions seen.
+    ///
+    /// # Details
+    /// Both channels changing at once means a sample was missed.
+    ///
+    /// # Returns
+    /// * `u32` - Invalid transition count
+    #[allow(dead_code)]
+    pub fn invalid_transitions(&self) -> u32 {
+        self.invalid
+    }
+
+    /// Records a step time and returns its acceleration multiplier.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `u32` - Multiplier between 1 and `accel_max`
+    fn step_multiplier(&mut self, now_ms: u64) -> u32 {
+        self.last_interval_ms = self
+            .last_step_ms
+            .map_or(u64::MAX, |last| now_ms.saturating_sub(last));
+        self.last_step_ms = Some(now_ms);
+        if self.last_interval_ms >= self.config.accel_ms {
+            return 1;
+        }
+        let multiplier = self.config.accel_ms / self.last_interval_ms.max(1);
+        multiplier.clamp(1, self.config.accel_max.max(1) as u64) as u32
+    }
+}
+
+/// Result of one rotary encoder sample.
+///
+/// # Fields
+/// * `delta` - Accelerated steps, positive clockwise
+/// * `push` - Debounced push switch event, if any
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct EncoderUpdate {
+    pub delta: i32,
+    pub push: Option<ButtonEvent>,
+}
+
+/// Rotary encoder with integrated push switch.
+///
+/// # Details
+/// The push switch is an ordinary active-low button handled by
+/// `ButtonController`, so it produces the same events as the user button.
+///
+/// # Fields
+/// * `encoder` - Quadrature decoder
+/// * `push` - Push switch debouncer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct RotaryEncoder {
+    encoder: Encoder,
+    push: ButtonController,
+}
+
+impl RotaryEncoder {
+    /// Creates rotary encoder with push switch.
+    ///
+    /// # Arguments
+    /// * `config` - Encoder configuration
+    /// * `push` - Push switch debouncer
+    ///
+    /// # Returns
+    /// * `Self` - New RotaryEncoder instance
+    #[allow(dead_code)]
+    pub fn new(config: EncoderConfig, push: ButtonController) -> Self {
+        Self {
+            encoder: Encoder::new(config),
+            push,
+        }
+    }
+
+    /// Updates the encoder and push switch with new samples.
+    ///
+    /// # Arguments
+    /// * `a_high` - true if channel A is high
+    /// * `b_high` - true if channel B is high
+    /// * `push_high` - true if the push switch GPIO is high (released)
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `EncoderUpdate` - Rotation and push events from this sample
+    #[allow(dead_code)]
+    pub fn update(
+        &mut self,
+        a_high: bool,
+        b_high: bool,
+        push_high: bool,
+        now_ms: u64,
+    ) -> EncoderUpdate {
+        EncoderUpdate {
+            delta: self.encoder.update(a_high, b_high, now_ms),
+            push: self.push.update(push_high),
+        }
+    }
+
+    /// Returns the quadrature decoder.
+    ///
+    /// # Returns
+    /// * `&Encoder` - Encoder state
+    #[allow(dead_code)]
+    pub fn encoder(&self) -> &Encoder {
+        &self.encoder
+    }
+
+    /// Returns the push switch debouncer.
+    ///
+    /// # Returns
+    /// * `&ButtonController` - Push switch state
+    #[allow(dead_code)]
+    pub fn push(&self) -> &ButtonController {
+        &self.push
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clockwise quadrature cycle from the idle detent (A leads B).
+    const CW: [(bool, bool); 4] = [(false, true), (false, false), (true, false), (true, true)];
+
+    /// Counter-clockwise quadrature cycle from the idle detent.
+    const CCW: [(bool, bool); 4] = [(true, false), (false, false), (false, true), (true, true)];
+
+    fn config(mode: StepMode) -> EncoderConfig {
+        EncoderConfig {
+            mode,
+            debounce_count: 1,
+            accel_ms: 40,
+            accel_max: 8,
+        }
+    }
+
+    /// Feeds each state for two samples so the debouncer confirms it.
+    fn feed(enc: &mut Encoder, states: &[(bool, bool)], now_ms: u64) -> i32 {
+        let mut total = 0;
+        for &(a, b) in states {
+            total += enc.update(a, b, now_ms);
+            total += enc.update(a, b, now_ms);
+        }
+        total
+    }
+
+    // ==================== Step Mode Tests ====================
+
+    #[test]
+    fn test_full_step_clockwise() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        assert_eq!(feed(&mut enc, &CW, 0), 1);
+        assert_eq!(enc.position(), 1);
+    }
+
+    #[test]
+    fn test_full_step_counter_clockwise() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        assert_eq!(feed(&mut enc, &CCW, 0), -1);
+        assert_eq!(enc.position(), -1);
+    }
+
+    #[test]
+    fn test_half_step_counts_two_per_cycle() {
+        let mut enc = Encoder::new(config(StepMode::Half));
+        feed(&mut enc, &CW, 0);
+        assert_eq!(enc.position(), 2);
+    }
+
+    #[test]
+    fn test_quarter_step_counts_four_per_cycle() {
+        let mut enc = Encoder::new(config(StepMode::Quarter));
+        feed(&mut enc, &CCW, 0);
+        assert_eq!(enc.position(), -4);
+    }
+
+    #[test]
+    fn test_partial_turn_discarded_at_detent() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        feed(&mut enc, &CW[..2], 0);
+        feed(&mut enc, &[(false, true), (true, true)], 0);
+        assert_eq!(enc.position(), 0);
+        assert_eq!(feed(&mut enc, &CW, 0), 1);
+    }
+
+    // ==================== Debounce Tests ====================
+
+    #[test]
+    fn test_single_channel_bounce_cancels() {
+        let mut enc = Encoder::new(config(StepMode::Quarter));
+        for _ in 0..10 {
+            feed(&mut enc, &[(false, true), (true, true)], 0);
+        }
+        assert_eq!(enc.position(), 0);
+    }
+
+    #[test]
+    fn test_one_sample_glitch_filtered() {
+        let mut enc = Encoder::new(config(StepMode::Quarter));
+        assert_eq!(enc.update(false, true, 0), 0);
+        assert_eq!(enc.update(true, true, 0), 0);
+        assert_eq!(enc.update(true, true, 0), 0);
+        assert_eq!(enc.position(), 0);
+    }
+
+    #[test]
+    fn test_invalid_transition_counted() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        feed(&mut enc, &[(false, false)], 0);
+        assert_eq!(enc.invalid_transitions(), 1);
+        assert_eq!(enc.position(), 0);
+    }
+
+    // ==================== Velocity Tests ====================
+
+    #[test]
+    fn test_slow_turn_not_accelerated() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        assert_eq!(feed(&mut enc, &CW, 0), 1);
+        assert_eq!(feed(&mut enc, &CW, 100), 1);
+    }
+
+    #[test]
+    fn test_fast_turn_accelerated() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        feed(&mut enc, &CW, 0);
+        assert_eq!(feed(&mut enc, &CW, 10), 4);
+        assert_eq!(feed(&mut enc, &CW, 11), 8);
+        assert_eq!(enc.position(), 3);
+    }
+
+    #[test]
+    fn test_velocity_and_decay() {
+        let mut enc = Encoder::new(config(StepMode::Full));
+        assert_eq!(enc.velocity(0), 0);
+        feed(&mut enc, &CCW, 0);
+        feed(&mut enc, &CCW, 50);
+        assert_eq!(enc.velocity(50), -20);
+        assert_eq!(enc.velocity(500), -2);
+    }
+
+    // ==================== Push Switch Tests ====================
+
+    #[test]
+    fn test_push_uses_button_controller() {
+        let mut rotary = RotaryEncoder::new(
+            config(StepMode::Full),
+            ButtonController::with_debounce_count(2),
+        );
+        assert_eq!(rotary.update(true, true, false, 0).push, None);
+        assert_eq!(rotary.update(true, true, false, 0).push, None);
+        let update = rotary.update(true, true, false, 0);
+        assert_eq!(update.push, Some(ButtonEvent::Pressed));
+        assert_eq!(update.delta, 0);
+        assert!(rotary.push().is_pressed());
+    }
+
+    #[test]
+    fn test_turn_while_pushed() {
+        let mut rotary = RotaryEncoder::new(
+            config(StepMode::Full),
+            ButtonController::with_debounce_count(1),
+        );
+        let mut delta = 0;
+        for &(a, b) in &CW {
+            delta += rotary.update(a, b, false, 0).delta;
+            delta += rotary.update(a, b, false, 0).delta;
+        }
+        assert_eq!(delta, 1);
+        assert!(rotary.push().is_pressed());
+        assert_eq!(rotary.encoder().position(), 1);
+    }
+}
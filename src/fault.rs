@@ -0,0 +1,446 @@
+/*
+ * @file fault.rs
+ * @brief Stuck-button and wiring fault detection
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: fault.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Button Wiring Fault Detection.
+//!
+//! BRIEF:
+//! Detects stuck (shorted) buttons and chattering inputs such as a
+//! missing pull-up. Suppresses events while faulted and recovers
+//! automatically once the input behaves again.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::ButtonEvent;
+use crate::config::{
+    CHATTER_MAX_EDGES, CHATTER_WINDOW_MS, FAULT_BLINK_MS, FAULT_PAUSE_MS, FAULT_RECOVERY_MS,
+    MAX_HOLD_MS,
+};
+use crate::led::LedPattern;
+
+/// Button wiring fault.
+///
+/// # Variants
+/// * `Stuck` - Press held beyond the maximum hold time
+/// * `Chatter` - Raw edge rate above the allowed limit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum Fault {
+    Stuck,
+    Chatter,
+}
+
+impl Fault {
+    /// Returns the LED pattern used to signal this fault.
+    ///
+    /// # Details
+    /// Uses the compiled fault timing from `config.rs`.
+    ///
+    /// # Returns
+    /// * `LedPattern` - Fault indication pattern
+    #[allow(dead_code)]
+    pub fn led_pattern(&self) -> LedPattern {
+        self.led_pattern_timed(FAULT_BLINK_MS, FAULT_PAUSE_MS)
+    }
+
+    /// Returns the LED pattern for this fault with the given timing.
+    ///
+    /// # Details
+    /// Stuck blinks continuously; chatter blinks in double pulses.
+    ///
+    /// # Arguments
+    /// * `blink_ms` - On and off time of each blink
+    /// * `pause_ms` - Pause between chatter bursts
+    ///
+    /// # Returns
+    /// * `LedPattern` - Fault indication pattern
+    #[allow(dead_code)]
+    pub fn led_pattern_timed(&self, blink_ms: u32, pause_ms: u32) -> LedPattern {
+        match self {
+            Fault::Stuck => LedPattern::Blink {
+                on_ms: blink_ms,
+                off_ms: blink_ms,
+            },
+            Fault::Chatter => LedPattern::Burst {
+                count: 2,
+                on_ms: blink_ms,
+                off_ms: blink_ms,
+                pause_ms,
+            },
+        }
+    }
+}
+
+/// Fault detection thresholds.
+///
+/// # Fields
+/// * `max_hold_ms` - Press duration that counts as stuck
+/// * `chatter_window_ms` - Raw edge counting window
+/// * `chatter_max_edges` - Edges allowed per window
+/// * `recovery_ms` - Quiet time required to clear chatter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct FaultConfig {
+    pub max_hold_ms: u64,
+    pub chatter_window_ms: u64,
+    pub chatter_max_edges: u32,
+    pub recovery_ms: u64,
+}
+
+impl Default for FaultConfig {
+    /// Returns thresholds from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default fault configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            max_hold_ms: MAX_HOLD_MS,
+            chatter_window_ms: CHATTER_WINDOW_MS,
+            chatter_max_edges: CHATTER_MAX_EDGES,
+            recovery_ms: FAULT_RECOVERY_MS,
+        }
+    }
+}
+
+/// Fault monitor and event gate.
+///
+/// # Details
+/// Observes raw samples and the debounced state, and reports
+/// press/release events only while no fault is active. On recovery
+/// an event is emitted if the debounced state changed while faulted.
+///
+/// # Fields
+/// * `config` - Detection thresholds
+/// * `reported_pressed` - Last state reported to consumers
+/// * `last_raw_high` - Previous raw sample
+/// * `pressed_since_ms` - Start of the current debounced press
+/// * `window_start_ms` - Start of the current edge counting window
+/// * `window_edges` - Raw edges seen in the current window
+/// * `last_edge_ms` - Timestamp of the most recent raw edge
+/// * `stuck` - Stuck fault active
+/// * `chatter` - Chatter fault active
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct FaultMonitor {
+    config: FaultConfig,
+    reported_pressed: bool,
+    last_raw_high: bool,
+    pressed_since_ms: Option<u64>,
+    window_start_ms: u64,
+    window_edges: u32,
+    last_edge_ms: u64,
+    stuck: bool,
+    chatter: bool,
+}
+
+impl Default for FaultMonitor {
+    /// Returns default FaultMonitor instance.
+    ///
+    /// # Details
+    /// Delegates to new() for initialization.
+    ///
+    /// # Returns
+    /// * `Self` - New FaultMonitor with default thresholds
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FaultMonitor {
+    /// Creates fault monitor with default thresholds.
+    ///
+    /// # Returns
+    /// * `Self` - New FaultMonitor instance
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self::with_config(FaultConfig::default())
+    }
+
+    /// Creates fault monitor with custom thresholds.
+    ///
+    /// # Arguments
+    /// * `config` - Detection thresholds
+    ///
+    /// # Returns
+    /// * `Self` - New FaultMonitor instance
+    #[allow(dead_code)]
+    pub fn with_config(config: FaultConfig) -> Self {
+        Self {
+            config,
+            reported_pressed: false,
+            last_raw_high: true,
+            pressed_since_ms: None,
+            window_start_ms: 0,
+            window_edges: 0,
+            last_edge_ms: 0,
+            stuck: false,
+            chatter: false,
+        }
+    }
+
+    /// Updates fault state and gates the debounced state.
+    ///
+    /// # Details
+    /// Call once per sample after updating the debouncer.
+    /// Returns an event whenever the debounced state differs from the
+    /// last reported state and no fault is active.
+    ///
+    /// # Arguments
+    /// * `gpio_high` - Raw GPIO sample
+    /// * `pressed` - Debounced state after this sample
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event to report to consumers
+    #[allow(dead_code)]
+    pub fn update(&mut self, gpio_high: bool, pressed: bool, now_ms: u64) -> Option<ButtonEvent> {
+        let before = self.fault();
+        self.track_edges(gpio_high, now_ms);
+        self.track_hold(pressed, now_ms);
+        let after = self.fault();
+        if after != before {
+            if let Some(fault) = after {
+                warn!("fault: {}", fault);
+            } else {
+                info!("fault: recovered");
+            }
+        }
+        if after.is_some() || pressed == self.reported_pressed {
+            return None;
+        }
+        self.reported_pressed = pressed;
+        Some(if pressed {
+            ButtonEvent::Pressed
+        } else {
+            ButtonEvent::Released
+        })
+    }
+
+    /// Returns the active fault, if any.
+    ///
+    /// # Details
+    /// Stuck takes precedence over chatter.
+    ///
+    /// # Returns
+    /// * `Option<Fault>` - Active fault
+    #[allow(dead_code)]
+    pub fn fault(&self) -> Option<Fault> {
+        if self.stuck {
+            Some(Fault::Stuck)
+        } else if self.chatter {
+            Some(Fault::Chatter)
+        } else {
+            None
+        }
+    }
+
+    /// Counts raw edges and updates the chatter fault.
+    fn track_edges(&mut self, gpio_high: bool, now_ms: u64) {
+        if now_ms.saturating_sub(self.window_start_ms) >= self.config.chatter_window_ms {
+            self.window_start_ms = now_ms;
+            self.window_edges = 0;
+        }
+        if gpio_high != self.last_raw_high {
+            self.last_raw_high = gpio_high;
+            self.last_edge_ms = now_ms;
+            self.window_edges = self.window_edges.saturating_add(1);
+            if self.window_edges > self.config.chatter_max_edges {
+                self.chatter = true;
+            }
+        } else if self.chatter
+            && now_ms.saturating_sub(self.last_edge_ms) >= self.config.recovery_ms
+        {
+            self.chatter = false;
+        }
+    }
+
+    /// Tracks press duration and updates the stuck fault.
+    fn track_hold(&mut self, pressed: bool, now_ms: u64) {
+        if !pressed {
+            self.pressed_since_ms = None;
+            self.stuck = false;
+            return;
+        }
+        let since = *self.pressed_since_ms.get_or_insert(now_ms);
+        if now_ms.saturating_sub(since) >= self.config.max_hold_ms {
+            self.stuck = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::led::LedState;
+
+    fn config() -> FaultConfig {
+        FaultConfig {
+            max_hold_ms: 1000,
+            chatter_window_ms: 100,
+            chatter_max_edges: 4,
+            recovery_ms: 200,
+        }
+    }
+
+    // ==================== Pass-Through Tests ====================
+
+    #[test]
+    fn test_new_monitor_no_fault() {
+        assert_eq!(FaultMonitor::new().fault(), None);
+    }
+
+    #[test]
+    fn test_default_config_from_constants() {
+        let cfg = FaultConfig::default();
+        assert_eq!(cfg.max_hold_ms, MAX_HOLD_MS);
+        assert_eq!(cfg.chatter_max_edges, CHATTER_MAX_EDGES);
+    }
+
+    #[test]
+    fn test_normal_press_passes_through() {
+        let mut mon = FaultMonitor::with_config(config());
+        assert_eq!(mon.update(false, false, 0), None);
+        assert_eq!(mon.update(false, true, 25), Some(ButtonEvent::Pressed));
+        assert_eq!(mon.update(false, true, 30), None);
+        assert_eq!(mon.update(true, false, 500), Some(ButtonEvent::Released));
+        assert_eq!(mon.fault(), None);
+    }
+
+    // ==================== Stuck Tests ====================
+
+    #[test]
+    fn test_stuck_after_max_hold() {
+        let mut mon = FaultMonitor::with_config(config());
+        mon.update(false, true, 0);
+        mon.update(false, true, 999);
+        assert_eq!(mon.fault(), None);
+        mon.update(false, true, 1000);
+        assert_eq!(mon.fault(), Some(Fault::Stuck));
+    }
+
+    #[test]
+    fn test_stuck_recovers_on_release() {
+        let mut mon = FaultMonitor::with_config(config());
+        assert_eq!(mon.update(false, true, 0), Some(ButtonEvent::Pressed));
+        mon.update(false, true, 2000);
+        assert_eq!(mon.fault(), Some(Fault::Stuck));
+        assert_eq!(mon.update(true, false, 3000), Some(ButtonEvent::Released));
+        assert_eq!(mon.fault(), None);
+    }
+
+    // ==================== Chatter Tests ====================
+
+    fn chatter(mon: &mut FaultMonitor, start: u64) -> u64 {
+        let mut t = start;
+        for i in 0..6 {
+            mon.update(i % 2 == 1, false, t);
+            t += 5;
+        }
+        t
+    }
+
+    #[test]
+    fn test_chatter_detected() {
+        let mut mon = FaultMonitor::with_config(config());
+        chatter(&mut mon, 0);
+        assert_eq!(mon.fault(), Some(Fault::Chatter));
+    }
+
+    #[test]
+    fn test_edges_spread_over_windows_not_chatter() {
+        let mut mon = FaultMonitor::with_config(config());
+        for i in 0..20u64 {
+            mon.update(i % 2 == 1, false, i * 50);
+        }
+        assert_eq!(mon.fault(), None);
+    }
+
+    #[test]
+    fn test_events_suppressed_during_chatter() {
+        let mut mon = FaultMonitor::with_config(config());
+        let t = chatter(&mut mon, 0);
+        assert_eq!(mon.update(false, true, t), None);
+        assert_eq!(mon.update(false, true, t + 50), None);
+    }
+
+    #[test]
+    fn test_chatter_recovers_after_quiet_time() {
+        let mut mon = FaultMonitor::with_config(config());
+        let t = chatter(&mut mon, 0);
+        mon.update(true, false, t + 100);
+        assert_eq!(mon.fault(), Some(Fault::Chatter));
+        mon.update(true, false, t + 300);
+        assert_eq!(mon.fault(), None);
+    }
+
+    #[test]
+    fn test_state_change_reported_after_recovery() {
+        let mut mon = FaultMonitor::with_config(config());
+        let t = chatter(&mut mon, 0);
+        assert_eq!(mon.update(false, true, t), None);
+        assert_eq!(mon.update(false, true, t + 300), Some(ButtonEvent::Pressed));
+    }
+
+    // ==================== LED Pattern Tests ====================
+
+    #[test]
+    fn test_fault_patterns_distinct() {
+        assert_ne!(Fault::Stuck.led_pattern(), Fault::Chatter.led_pattern());
+    }
+
+    #[test]
+    fn test_fault_patterns_blink() {
+        for fault in [Fault::Stuck, Fault::Chatter] {
+            let pattern = fault.led_pattern();
+            assert_eq!(pattern.state_at(0), LedState::On);
+            assert_eq!(pattern.state_at(FAULT_BLINK_MS as u64), LedState::Off);
+        }
+    }
+
+    #[test]
+    fn test_fault_pattern_uses_given_timing() {
+        let pattern = Fault::Chatter.led_pattern_timed(40, 300);
+        assert_eq!(pattern.state_at(39), LedState::On);
+        assert_eq!(pattern.state_at(40), LedState::Off);
+        assert_eq!(pattern.period_ms(), 4 * 40 + 300);
+        assert_eq!(
+            Fault::Stuck.led_pattern_timed(FAULT_BLINK_MS, FAULT_PAUSE_MS),
+            Fault::Stuck.led_pattern()
+        );
+    }
+}
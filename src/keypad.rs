@@ -0,0 +1,508 @@
+/*
+ * @file keypad.rs
+ * @brief Debounced keypad matrix scanner
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: keypad.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Keypad Matrix Scanner.
+//!
+//! BRIEF:
+//! Scans 3x4, 4x4 and other row/column key matrices one row at a time.
+//! Each key is debounced by its own `ButtonController`. Scans showing a
+//! ghosting rectangle are discarded and simultaneous presses are
+//! limited to a configurable rollover count.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{ButtonController, ButtonEvent};
+use crate::config::{DEBOUNCE_COUNT, KEYPAD_MAX_KEYS};
+
+/// Legends of a standard 3x4 telephone keypad.
+#[allow(dead_code)]
+pub const KEYMAP_3X4: [[char; 3]; 4] = [
+    ['1', '2', '3'],
+    ['4', '5', '6'],
+    ['7', '8', '9'],
+    ['*', '0', '#'],
+];
+
+/// Legends of a standard 4x4 keypad.
+#[allow(dead_code)]
+pub const KEYMAP_4X4: [[char; 4]; 4] = [
+    ['1', '2', '3', 'A'],
+    ['4', '5', '6', 'B'],
+    ['7', '8', '9', 'C'],
+    ['*', '0', '#', 'D'],
+];
+
+/// Keypad matrix pin access.
+///
+/// # Details
+/// Rows are driven low one at a time; columns are pulled up and read
+/// low where a key connects them to the selected row. Implementations
+/// should allow the lines to settle before `read_column` returns.
+#[allow(dead_code)]
+pub trait MatrixPins {
+    /// Drives `row` low and releases all other rows.
+    fn select_row(&mut self, row: usize);
+
+    /// Returns true if column `col` reads high.
+    fn read_column(&mut self, col: usize) -> bool;
+
+    /// Releases all rows.
+    fn release_rows(&mut self);
+}
+
+/// Debounced key event with matrix position.
+///
+/// # Fields
+/// * `row` - Key row
+/// * `col` - Key column
+/// * `event` - Debounced press or release
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct KeyEvent {
+    pub row: u8,
+    pub col: u8,
+    pub event: ButtonEvent,
+}
+
+impl KeyEvent {
+    /// Looks up the key legend in a keymap.
+    ///
+    /// # Arguments
+    /// * `keymap` - Legends indexed by row then column
+    ///
+    /// # Returns
+    /// * `Option<char>` - Legend, None if outside the keymap
+    #[allow(dead_code)]
+    pub fn key<const ROWS: usize, const COLS: usize>(
+        &self,
+        keymap: &[[char; COLS]; ROWS],
+    ) -> Option<char> {
+        keymap
+            .get(self.row as usize)
+            .and_then(|row| row.get(self.col as usize))
+            .copied()
+    }
+}
+
+/// Keypad scanner configuration.
+///
+/// # Fields
+/// * `debounce_count` - Stable scans required per key
+/// * `max_keys` - Maximum keys held at once (n-key rollover limit)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct KeypadConfig {
+    pub debounce_count: u32,
+    pub max_keys: usize,
+}
+
+impl Default for KeypadConfig {
+    /// Returns configuration from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default keypad configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            debounce_count: DEBOUNCE_COUNT,
+            max_keys: KEYPAD_MAX_KEYS,
+        }
+    }
+}
+
+/// Keypad matrix scanner.
+///
+/// # Details
+/// `ROWS` x `COLS` matrix with up to 32 columns. Without per-key
+/// diodes, three keys pressed on the corners of a rectangle make the
+/// fourth corner read as pressed; such scans are discarded so phantom
+/// keys never reach the debouncers.
+///
+/// # Fields
+/// * `keys` - Per-key debouncers indexed by row then column
+/// * `max_keys` - Rollover limit
+/// * `held` - Keys currently debounced as pressed
+/// * `ghost_scans` - Scans discarded because of ghosting
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct Keypad<const ROWS: usize, const COLS: usize> {
+    keys: [[ButtonController; COLS]; ROWS],
+    max_keys: usize,
+    held: usize,
+    ghost_scans: u32,
+}
+
+/// Standard 3x4 telephone keypad (4 rows, 3 columns).
+#[allow(dead_code)]
+pub type Keypad3x4 = Keypad<4, 3>;
+
+/// Standard 4x4 keypad.
+#[allow(dead_code)]
+pub type Keypad4x4 = Keypad<4, 4>;
+
+impl<const ROWS: usize, const COLS: usize> Keypad<ROWS, COLS> {
+    /// Creates keypad scanner with all keys released.
+    ///
+    /// # Arguments
+    /// * `config` - Keypad configuration
+    ///
+    /// # Returns
+    /// * `Self` - New Keypad instance
+    #[allow(dead_code)]
+    pub fn new(config: KeypadConfig) -> Self {
+        const { assert!(COLS <= 32, "keypad supports up to 32 columns") };
+        Self {
+            keys: [[ButtonController::with_debounce_count(config.debounce_count); COLS]; ROWS],
+            max_keys: config.max_keys,
+            held: 0,
+            ghost_scans: 0,
+        }
+    }
+
+    /// Scans the whole matrix once.
+    ///
+    /// # Arguments
+    /// * `pins` - Matrix pin access
+    /// * `on_event` - Called for each debounced key event
+    #[allow(dead_code)]
+    pub fn scan(&mut self, pins: &mut impl MatrixPins, on_event: impl FnMut(KeyEvent)) {
+        let mut active = [0u32; ROWS];
+        for (row, mask) in active.iter_mut().enumerate() {
+            pins.select_row(row);
+            for col in 0..COLS {
+                if !pins.read_column(col) {
+                    *mask |= 1 << col;
+                }
+            }
+        }
+        pins.release_rows();
+        self.process(&active, on_event);
+    }
+
+    /// Processes one raw scan.
+    ///
+    /// # Details
+    /// Discards ghosted scans, then updates every key. Keys not yet
+    /// held are fed as released while the rollover limit is reached.
+    ///
+    /// # Arguments
+    /// * `active` - Per-row bit mask of columns reading pressed
+    /// * `on_event` - Called for each debounced key event
+    #[allow(dead_code)]
+    pub fn process(&mut self, active: &[u32; ROWS], mut on_event: impl FnMut(KeyEvent)) {
+        if Self::is_ghosted(active) {
+            self.ghost_scans = self.ghost_scans.saturating_add(1);
+            return;
+        }
+        for (row, keys) in self.keys.iter_mut().enumerate() {
+            for (col, key) in keys.iter_mut().enumerate() {
+                let mut pressed = active[row] & (1 << col) != 0;
+                if pressed && !key.is_pressed() && self.held >= self.max_keys {
+                    pressed = false;
+                }
+                let Some(event) = key.update(!pressed) else {
+                    continue;
+                };
+                match event {
+                    ButtonEvent::Pressed => self.held += 1,
+                    ButtonEvent::Released => self.held -= 1,
+                }
+                on_event(KeyEvent {
+                    row: row as u8,
+                    col: col as u8,
+                    event,
+                });
+            }
+        }
+    }
+
+    /// Returns true if a key is debounced as pressed.
+    ///
+    /// # Arguments
+    /// * `row` - Key row
+    /// * `col` - Key column
+    ///
+    /// # Returns
+    /// * `bool` - true if pressed, false if released or out of range
+    #[allow(dead_code)]
+    pub fn is_pressed(&self, row: usize, col: usize) -> bool {
+        self.keys
+            .get(row)
+            .and_then(|keys| keys.get(col))
+            .is_some_and(|key| key.is_pressed())
+    }
+
+    /// Returns number of keys currently held.
+    ///
+    /// # Returns
+    /// * `usize` - Held key count
+    #[allow(dead_code)]
+    pub fn held_keys(&self) -> usize {
+        self.held
+    }
+
+    /// Returns number of scans discarded because of ghosting.
+    ///
+    /// # Returns
+    /// * `u32` - Ghosted scan count
+    #[allow(dead_code)]
+    pub fn ghost_scans(&self) -> u32 {
+        self.ghost_scans
+    }
+
+    /// Returns true if any two rows share two or more active columns.
+    ///
+    /// # Arguments
+    /// * `active` - Per-row bit mask of columns reading pressed
+    ///
+    /// # Returns
+    /// * `bool` - true if the scan may contain phantom keys
+    fn is_ghosted(active: &[u32; ROWS]) -> bool {
+        active.iter().enumerate().any(|(row, &mask)| {
+            active[row + 1..]
+                .iter()
+                .any(|&other| (mask & other).count_ones() >= 2)
+        })
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Default for Keypad<ROWS, COLS> {
+    /// Returns keypad with default configuration.
+    ///
+    /// # Returns
+    /// * `Self` - New Keypad with default values
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new(KeypadConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulated diode-less key matrix.
+    ///
+    /// # Details
+    /// A column reads low when any chain of pressed keys connects it to
+    /// the selected row, which reproduces ghosting.
+    struct SimMatrix<const R: usize, const C: usize> {
+        pressed: [[bool; C]; R],
+        selected: Option<usize>,
+    }
+
+    impl<const R: usize, const C: usize> SimMatrix<R, C> {
+        fn new() -> Self {
+            Self {
+                pressed: [[false; C]; R],
+                selected: None,
+            }
+        }
+    }
+
+    impl<const R: usize, const C: usize> MatrixPins for SimMatrix<R, C> {
+        fn select_row(&mut self, row: usize) {
+            self.selected = Some(row);
+        }
+
+        fn read_column(&mut self, col: usize) -> bool {
+            let Some(start) = self.selected else {
+                return true;
+            };
+            let mut rows = [false; R];
+            let mut cols = [false; C];
+            rows[start] = true;
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for (r, keys) in self.pressed.iter().enumerate() {
+                    for (c, &pressed) in keys.iter().enumerate() {
+                        if pressed && rows[r] != cols[c] {
+                            rows[r] = true;
+                            cols[c] = true;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            !cols[col]
+        }
+
+        fn release_rows(&mut self) {
+            self.selected = None;
+        }
+    }
+
+    fn config(max_keys: usize) -> KeypadConfig {
+        KeypadConfig {
+            debounce_count: 2,
+            max_keys,
+        }
+    }
+
+    fn scan_n<const R: usize, const C: usize>(
+        keypad: &mut Keypad<R, C>,
+        matrix: &mut SimMatrix<R, C>,
+        n: usize,
+    ) -> Vec<KeyEvent> {
+        let mut events = Vec::new();
+        for _ in 0..n {
+            keypad.scan(matrix, |e| events.push(e));
+        }
+        events
+    }
+
+    // ==================== Scan Tests ====================
+
+    #[test]
+    fn test_idle_matrix_no_events() {
+        let mut keypad = Keypad3x4::new(config(4));
+        let mut matrix = SimMatrix::<4, 3>::new();
+        assert!(scan_n(&mut keypad, &mut matrix, 10).is_empty());
+    }
+
+    #[test]
+    fn test_press_reports_row_and_column() {
+        let mut keypad = Keypad4x4::new(config(4));
+        let mut matrix = SimMatrix::<4, 4>::new();
+        matrix.pressed[2][3] = true;
+        let events = scan_n(&mut keypad, &mut matrix, 3);
+        assert_eq!(
+            events,
+            [KeyEvent {
+                row: 2,
+                col: 3,
+                event: ButtonEvent::Pressed
+            }]
+        );
+        assert_eq!(events[0].key(&KEYMAP_4X4), Some('C'));
+        assert!(keypad.is_pressed(2, 3));
+    }
+
+    #[test]
+    fn test_release_reported() {
+        let mut keypad = Keypad3x4::new(config(4));
+        let mut matrix = SimMatrix::<4, 3>::new();
+        matrix.pressed[3][1] = true;
+        scan_n(&mut keypad, &mut matrix, 3);
+        matrix.pressed[3][1] = false;
+        let events = scan_n(&mut keypad, &mut matrix, 3);
+        assert_eq!(events[0].event, ButtonEvent::Released);
+        assert_eq!(events[0].key(&KEYMAP_3X4), Some('0'));
+        assert_eq!(keypad.held_keys(), 0);
+    }
+
+    #[test]
+    fn test_per_key_debounce() {
+        let mut keypad = Keypad3x4::new(config(4));
+        let mut matrix = SimMatrix::<4, 3>::new();
+        for _ in 0..5 {
+            matrix.pressed[0][0] = true;
+            keypad.scan(&mut matrix, |_| {});
+            matrix.pressed[0][0] = false;
+            keypad.scan(&mut matrix, |_| {});
+        }
+        assert!(!keypad.is_pressed(0, 0));
+    }
+
+    #[test]
+    fn test_out_of_range_not_pressed() {
+        let keypad = Keypad3x4::default();
+        assert!(!keypad.is_pressed(4, 0));
+        assert!(!keypad.is_pressed(0, 3));
+    }
+
+    // ==================== Ghosting Tests ====================
+
+    #[test]
+    fn test_two_keys_same_row_not_ghosted() {
+        let mut keypad = Keypad4x4::new(config(4));
+        let mut matrix = SimMatrix::<4, 4>::new();
+        matrix.pressed[1][0] = true;
+        matrix.pressed[1][2] = true;
+        assert_eq!(scan_n(&mut keypad, &mut matrix, 3).len(), 2);
+        assert_eq!(keypad.ghost_scans(), 0);
+    }
+
+    #[test]
+    fn test_rectangle_discarded() {
+        let mut keypad = Keypad4x4::new(config(4));
+        let mut matrix = SimMatrix::<4, 4>::new();
+        matrix.pressed[0][0] = true;
+        matrix.pressed[0][1] = true;
+        matrix.pressed[1][0] = true;
+        let events = scan_n(&mut keypad, &mut matrix, 3);
+        assert!(events.is_empty());
+        assert!(!keypad.is_pressed(1, 1));
+        assert_eq!(keypad.ghost_scans(), 3);
+    }
+
+    #[test]
+    fn test_ghost_detection_on_raw_masks() {
+        assert!(Keypad4x4::is_ghosted(&[0b0011, 0b0011, 0, 0]));
+        assert!(!Keypad4x4::is_ghosted(&[0b0011, 0b0100, 0b0100, 0]));
+    }
+
+    // ==================== Rollover Tests ====================
+
+    #[test]
+    fn test_rollover_limit_blocks_extra_keys() {
+        let mut keypad = Keypad4x4::new(config(2));
+        let mut matrix = SimMatrix::<4, 4>::new();
+        matrix.pressed[0][0] = true;
+        matrix.pressed[1][1] = true;
+        matrix.pressed[2][2] = true;
+        let events = scan_n(&mut keypad, &mut matrix, 5);
+        assert_eq!(events.len(), 2);
+        assert_eq!(keypad.held_keys(), 2);
+        assert!(!keypad.is_pressed(2, 2));
+    }
+
+    #[test]
+    fn test_blocked_key_accepted_after_release() {
+        let mut keypad = Keypad4x4::new(config(1));
+        let mut matrix = SimMatrix::<4, 4>::new();
+        matrix.pressed[0][0] = true;
+        scan_n(&mut keypad, &mut matrix, 3);
+        matrix.pressed[3][3] = true;
+        scan_n(&mut keypad, &mut matrix, 3);
+        assert!(!keypad.is_pressed(3, 3));
+        matrix.pressed[0][0] = false;
+        scan_n(&mut keypad, &mut matrix, 6);
+        assert!(keypad.is_pressed(3, 3));
+        assert!(!keypad.is_pressed(0, 0));
+    }
+}
@@ -0,0 +1,422 @@
+/*
+ * @file ladder.rs
+ * @brief Resistor-ladder multi-button input on one ADC pin
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: ladder.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Resistor-Ladder Button Input.
+//!
+//! BRIEF:
+//! Maps ADC readings from a resistor ladder to button IDs using the
+//! nearest calibrated level with hysteresis. Each button is debounced
+//! by its own `ButtonController`, so ladder buttons produce the same
+//! events as GPIO buttons.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{ButtonController, ButtonEvent};
+use crate::config::{DEBOUNCE_COUNT, LADDER_HYSTERESIS, LADDER_IDLE_LEVEL, LADDER_LEVELS};
+
+/// Debounced ladder button event.
+///
+/// # Fields
+/// * `button` - Button index into the ladder levels
+/// * `event` - Debounced press or release
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct LadderEvent {
+    pub button: u8,
+    pub event: ButtonEvent,
+}
+
+/// Resistor ladder configuration.
+///
+/// # Fields
+/// * `levels` - Nominal ADC reading of each button
+/// * `idle` - Nominal ADC reading with no button pressed
+/// * `hysteresis` - Margin required to change classification
+/// * `debounce_count` - Stable samples required per button
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct LadderConfig<const N: usize> {
+    pub levels: [u16; N],
+    pub idle: u16,
+    pub hysteresis: u16,
+    pub debounce_count: u32,
+}
+
+impl<const N: usize> LadderConfig<N> {
+    /// Returns true if all levels are far enough apart.
+    ///
+    /// # Details
+    /// Every pair of levels, including idle, must differ by more than
+    /// twice the hysteresis so each window has a stable interior.
+    ///
+    /// # Returns
+    /// * `bool` - true if the configuration is usable
+    #[allow(dead_code)]
+    pub fn is_valid(&self) -> bool {
+        let min_gap = 2 * self.hysteresis as u32;
+        self.levels.iter().enumerate().all(|(i, &level)| {
+            (level.abs_diff(self.idle) as u32) > min_gap
+                && self.levels[i + 1..]
+                    .iter()
+                    .all(|&other| (level.abs_diff(other) as u32) > min_gap)
+        })
+    }
+}
+
+impl Default for LadderConfig<5> {
+    /// Returns the five-button ladder from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default ladder configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            levels: LADDER_LEVELS,
+            idle: LADDER_IDLE_LEVEL,
+            hysteresis: LADDER_HYSTERESIS,
+            debounce_count: DEBOUNCE_COUNT,
+        }
+    }
+}
+
+/// Resistor-ladder button input.
+///
+/// # Details
+/// A reading is classified as the nearest level, idle included, but
+/// the classification only changes when the new level is closer than
+/// the current one by more than the hysteresis. The classified button
+/// is fed to its debouncer as pressed and all others as released.
+///
+/// # Fields
+/// * `config` - Ladder configuration
+/// * `buttons` - Per-button debouncers
+/// * `current` - Classified button, None when idle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct AdcLadder<const N: usize> {
+    config: LadderConfig<N>,
+    buttons: [ButtonController; N],
+    current: Option<usize>,
+}
+
+impl<const N: usize> AdcLadder<N> {
+    /// Creates ladder input with all buttons released.
+    ///
+    /// # Arguments
+    /// * `config` - Ladder configuration
+    ///
+    /// # Returns
+    /// * `Self` - New AdcLadder instance
+    #[allow(dead_code)]
+    pub fn new(config: LadderConfig<N>) -> Self {
+        const { assert!(N <= u8::MAX as usize, "too many ladder buttons") };
+        Self {
+            config,
+            buttons: [ButtonController::with_debounce_count(config.debounce_count); N],
+            current: None,
+        }
+    }
+
+    /// Updates the ladder with a new ADC reading.
+    ///
+    /// # Arguments
+    /// * `reading` - Raw ADC reading
+    /// * `on_event` - Called for each debounced button event
+    #[allow(dead_code)]
+    pub fn update(&mut self, reading: u16, mut on_event: impl FnMut(LadderEvent)) {
+        self.current = self.classify(reading);
+        for (index, button) in self.buttons.iter_mut().enumerate() {
+            if let Some(event) = button.update(self.current != Some(index)) {
+                on_event(LadderEvent {
+                    button: index as u8,
+                    event,
+                });
+            }
+        }
+    }
+
+    /// Replaces a button level with a measured reading.
+    ///
+    /// # Details
+    /// The change is rejected if it would leave two levels too close.
+    ///
+    /// # Arguments
+    /// * `button` - Button index
+    /// * `reading` - ADC reading measured while the button is held
+    ///
+    /// # Returns
+    /// * `bool` - true if the calibration was applied
+    #[allow(dead_code)]
+    pub fn calibrate(&mut self, button: usize, reading: u16) -> bool {
+        let mut config = self.config;
+        let Some(level) = config.levels.get_mut(button) else {
+            return false;
+        };
+        *level = reading;
+        self.apply(config)
+    }
+
+    /// Replaces the idle level with a measured reading.
+    ///
+    /// # Arguments
+    /// * `reading` - ADC reading measured with no button held
+    ///
+    /// # Returns
+    /// * `bool` - true if the calibration was applied
+    #[allow(dead_code)]
+    pub fn calibrate_idle(&mut self, reading: u16) -> bool {
+        let mut config = self.config;
+        config.idle = reading;
+        self.apply(config)
+    }
+
+    /// Returns the current configuration.
+    ///
+    /// # Returns
+    /// * `&LadderConfig<N>` - Ladder configuration
+    #[allow(dead_code)]
+    pub fn config(&self) -> &LadderConfig<N> {
+        &self.config
+    }
+
+    /// Returns the debouncer of a button.
+    ///
+    /// # Arguments
+    /// * `button` - Button index
+    ///
+    /// # Returns
+    /// * `Option<&ButtonController>` - Button state, None if out of range
+    #[allow(dead_code)]
+    pub fn button(&self, button: usize) -> Option<&ButtonController> {
+        self.buttons.get(button)
+    }
+
+    /// Returns the classified button before debouncing.
+    ///
+    /// # Returns
+    /// * `Option<usize>` - Button index, None when idle
+    #[allow(dead_code)]
+    pub fn classified(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Applies a configuration if it is valid.
+    ///
+    /// # Arguments
+    /// * `config` - Candidate configuration
+    ///
+    /// # Returns
+    /// * `bool` - true if applied
+    fn apply(&mut self, config: LadderConfig<N>) -> bool {
+        if !config.is_valid() {
+            return false;
+        }
+        self.config = config;
+        true
+    }
+
+    /// Returns the level of a classification.
+    ///
+    /// # Arguments
+    /// * `button` - Button index, None for idle
+    ///
+    /// # Returns
+    /// * `u16` - Nominal ADC reading
+    fn level(&self, button: Option<usize>) -> u16 {
+        button.map_or(self.config.idle, |index| self.config.levels[index])
+    }
+
+    /// Classifies a reading with hysteresis.
+    ///
+    /// # Arguments
+    /// * `reading` - Raw ADC reading
+    ///
+    /// # Returns
+    /// * `Option<usize>` - Button index, None when idle
+    fn classify(&self, reading: u16) -> Option<usize> {
+        let nearest = (0..N)
+            .map(Some)
+            .chain(core::iter::once(None))
+            .min_by_key(|&button| reading.abs_diff(self.level(button)))
+            .flatten();
+        let current_distance = reading.abs_diff(self.level(self.current)) as u32;
+        let nearest_distance = reading.abs_diff(self.level(nearest)) as u32;
+        if nearest_distance + (self.config.hysteresis as u32) < current_distance {
+            nearest
+        } else {
+            self.current
+        }
+    }
+}
+
+impl Default for AdcLadder<5> {
+    /// Returns five-button ladder with default configuration.
+    ///
+    /// # Returns
+    /// * `Self` - New AdcLadder with default values
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new(LadderConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ladder() -> AdcLadder<5> {
+        AdcLadder::new(LadderConfig {
+            debounce_count: 2,
+            ..LadderConfig::default()
+        })
+    }
+
+    fn feed(ladder: &mut AdcLadder<5>, reading: u16, n: usize) -> Vec<LadderEvent> {
+        let mut events = Vec::new();
+        for _ in 0..n {
+            ladder.update(reading, |e| events.push(e));
+        }
+        events
+    }
+
+    fn event(button: u8, event: ButtonEvent) -> LadderEvent {
+        LadderEvent { button, event }
+    }
+
+    // ==================== Configuration Tests ====================
+
+    #[test]
+    fn test_default_config_valid() {
+        assert!(LadderConfig::default().is_valid());
+    }
+
+    #[test]
+    fn test_overlapping_levels_invalid() {
+        let config = LadderConfig {
+            levels: [0, 50],
+            idle: 4095,
+            hysteresis: 30,
+            debounce_count: 1,
+        };
+        assert!(!config.is_valid());
+    }
+
+    // ==================== Classification Tests ====================
+
+    #[test]
+    fn test_idle_reading_no_events() {
+        let mut ladder = ladder();
+        assert!(feed(&mut ladder, 4090, 10).is_empty());
+        assert_eq!(ladder.classified(), None);
+    }
+
+    #[test]
+    fn test_reading_maps_to_button() {
+        let mut ladder = ladder();
+        let events = feed(&mut ladder, 1320, 3);
+        assert_eq!(events, [event(2, ButtonEvent::Pressed)]);
+        assert!(ladder.button(2).unwrap().is_pressed());
+    }
+
+    #[test]
+    fn test_release_reported() {
+        let mut ladder = ladder();
+        feed(&mut ladder, 10, 3);
+        let events = feed(&mut ladder, 4095, 3);
+        assert_eq!(events, [event(0, ButtonEvent::Released)]);
+    }
+
+    #[test]
+    fn test_hysteresis_holds_at_boundary() {
+        let mut ladder = ladder();
+        feed(&mut ladder, 2048, 3);
+        let midpoint = (2048 + 2816) / 2;
+        assert!(feed(&mut ladder, midpoint + 20, 10).is_empty());
+        assert_eq!(ladder.classified(), Some(3));
+        feed(&mut ladder, midpoint + 60, 1);
+        assert_eq!(ladder.classified(), Some(4));
+    }
+
+    #[test]
+    fn test_slide_between_buttons() {
+        let mut ladder = ladder();
+        feed(&mut ladder, 624, 3);
+        let events = feed(&mut ladder, 2048, 3);
+        assert_eq!(
+            events,
+            [
+                event(1, ButtonEvent::Released),
+                event(3, ButtonEvent::Pressed)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_noise_spike_debounced() {
+        let mut ladder = ladder();
+        for _ in 0..5 {
+            feed(&mut ladder, 0, 1);
+            feed(&mut ladder, 4095, 1);
+        }
+        assert!(!ladder.button(0).unwrap().is_pressed());
+    }
+
+    // ==================== Calibration Tests ====================
+
+    #[test]
+    fn test_calibration_moves_window() {
+        let mut ladder = ladder();
+        assert!(ladder.calibrate(1, 800));
+        feed(&mut ladder, 820, 3);
+        assert_eq!(ladder.classified(), Some(1));
+    }
+
+    #[test]
+    fn test_calibration_rejects_overlap() {
+        let mut ladder = ladder();
+        assert!(!ladder.calibrate(1, 1300));
+        assert_eq!(ladder.config().levels[1], 624);
+        assert!(!ladder.calibrate(9, 100));
+    }
+
+    #[test]
+    fn test_idle_calibration() {
+        let mut ladder = ladder();
+        assert!(ladder.calibrate_idle(3700));
+        assert!(feed(&mut ladder, 3650, 5).is_empty());
+        assert!(!ladder.calibrate_idle(2800));
+    }
+}
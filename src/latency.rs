@@ -0,0 +1,449 @@
+/*
+ * @file latency.rs
+ * @brief Event timestamps and debounce latency histogram
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: latency.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Button Event Latency.
+//!
+//! BRIEF:
+//! Stamps debounced button events with the time of the first raw edge
+//! and of debounce confirmation, and collects the debounce-induced
+//! latency into a histogram. Times are HAL-agnostic tick counts from
+//! any monotonic clock, such as `embassy_time::Instant::as_ticks`.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{ButtonController, ButtonEvent};
+
+/// Debounced button event with timestamps.
+///
+/// # Fields
+/// * `event` - Debounced press or release
+/// * `edge_ticks` - Sample time of the first raw edge of the transition
+/// * `confirmed_ticks` - Sample time the debouncer confirmed it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct TimedEvent {
+    pub event: ButtonEvent,
+    pub edge_ticks: u64,
+    pub confirmed_ticks: u64,
+}
+
+impl TimedEvent {
+    /// Returns the latency added by debouncing.
+    ///
+    /// # Returns
+    /// * `u64` - Ticks from first edge to confirmation
+    #[allow(dead_code)]
+    pub fn latency_ticks(&self) -> u64 {
+        self.confirmed_ticks.saturating_sub(self.edge_ticks)
+    }
+}
+
+/// First-edge tracker for a `ButtonController`.
+///
+/// # Details
+/// Observes the controller after every update and follows the state
+/// of the emitted events. The first raw sample that differs from the
+/// last emitted state starts a transition; bounces keep the original
+/// edge time, and the edge is forgotten if the input settles back
+/// without an event. An eager lock-out after an event is not a
+/// transition, so it never adds to the measured latency. Edge times
+/// are quantised to the sampling interval.
+///
+/// # Fields
+/// * `edge_ticks` - Start of the pending transition, if any
+/// * `reported_pressed` - State of the last emitted event
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct EdgeTimer {
+    edge_ticks: Option<u64>,
+    reported_pressed: bool,
+}
+
+impl EdgeTimer {
+    /// Creates edge timer with no pending transition.
+    ///
+    /// # Returns
+    /// * `Self` - New EdgeTimer instance
+    #[allow(dead_code)]
+    pub const fn new() -> Self {
+        Self {
+            edge_ticks: None,
+            reported_pressed: false,
+        }
+    }
+
+    /// Observes the controller after an update.
+    ///
+    /// # Arguments
+    /// * `controller` - Controller that was just updated
+    /// * `event` - Event returned by that update, if any
+    /// * `now_ticks` - Sample time in ticks
+    ///
+    /// # Returns
+    /// * `Option<TimedEvent>` - Timestamped event if one was emitted
+    #[allow(dead_code)]
+    pub fn observe(
+        &mut self,
+        controller: &ButtonController,
+        event: Option<ButtonEvent>,
+        now_ticks: u64,
+    ) -> Option<TimedEvent> {
+        if let Some(event) = event {
+            self.reported_pressed = event == ButtonEvent::Pressed;
+            return Some(TimedEvent {
+                event,
+                edge_ticks: self.edge_ticks.take().unwrap_or(now_ticks),
+                confirmed_ticks: now_ticks,
+            });
+        }
+        let raw_changed = controller.is_raw_pressed() != self.reported_pressed;
+        if !raw_changed && controller.is_settled() {
+            self.edge_ticks = None;
+        } else if raw_changed && self.edge_ticks.is_none() {
+            self.edge_ticks = Some(now_ticks);
+        }
+        None
+    }
+}
+
+/// Fixed-bucket latency histogram.
+///
+/// # Details
+/// Bucket `i` counts latencies in `[i, i + 1) * bucket_ticks`; the last
+/// bucket also counts everything longer.
+///
+/// # Fields
+/// * `bucket_ticks` - Width of each bucket
+/// * `buckets` - Sample count per bucket
+/// * `count` - Total samples
+/// * `total_ticks` - Sum of all latencies
+/// * `max_ticks` - Largest latency seen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct LatencyHistogram<const N: usize> {
+    bucket_ticks: u64,
+    buckets: [u32; N],
+    count: u32,
+    total_ticks: u64,
+    max_ticks: u64,
+}
+
+impl<const N: usize> LatencyHistogram<N> {
+    /// Creates empty histogram.
+    ///
+    /// # Details
+    /// A bucket width of zero is treated as one tick.
+    ///
+    /// # Arguments
+    /// * `bucket_ticks` - Width of each bucket in ticks
+    ///
+    /// # Returns
+    /// * `Self` - New LatencyHistogram instance
+    #[allow(dead_code)]
+    pub fn new(bucket_ticks: u64) -> Self {
+        const { assert!(N > 0, "histogram needs at least one bucket") };
+        Self {
+            bucket_ticks: bucket_ticks.max(1),
+            buckets: [0; N],
+            count: 0,
+            total_ticks: 0,
+            max_ticks: 0,
+        }
+    }
+
+    /// Records one latency.
+    ///
+    /// # Arguments
+    /// * `latency_ticks` - Latency in ticks
+    #[allow(dead_code)]
+    pub fn record(&mut self, latency_ticks: u64) {
+        let index = (latency_ticks / self.bucket_ticks).min(N as u64 - 1) as usize;
+        self.buckets[index] = self.buckets[index].saturating_add(1);
+        self.count = self.count.saturating_add(1);
+        self.total_ticks = self.total_ticks.saturating_add(latency_ticks);
+        self.max_ticks = self.max_ticks.max(latency_ticks);
+    }
+
+    /// Records the latency of a timestamped event.
+    ///
+    /// # Arguments
+    /// * `event` - Timestamped event
+    #[allow(dead_code)]
+    pub fn record_event(&mut self, event: &TimedEvent) {
+        self.record(event.latency_ticks());
+    }
+
+    /// Returns sample count per bucket.
+    ///
+    /// # Returns
+    /// * `&[u32; N]` - Bucket counts
+    #[allow(dead_code)]
+    pub fn buckets(&self) -> &[u32; N] {
+        &self.buckets
+    }
+
+    /// Returns bucket width.
+    ///
+    /// # Returns
+    /// * `u64` - Ticks per bucket
+    #[allow(dead_code)]
+    pub fn bucket_ticks(&self) -> u64 {
+        self.bucket_ticks
+    }
+
+    /// Returns number of recorded latencies.
+    ///
+    /// # Returns
+    /// * `u32` - Sample count
+    #[allow(dead_code)]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns largest recorded latency.
+    ///
+    /// # Returns
+    /// * `u64` - Maximum latency in ticks, 0 if empty
+    #[allow(dead_code)]
+    pub fn max_ticks(&self) -> u64 {
+        self.max_ticks
+    }
+
+    /// Returns mean latency.
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Mean latency in ticks, None if empty
+    #[allow(dead_code)]
+    pub fn average_ticks(&self) -> Option<u64> {
+        (self.count > 0).then(|| self.total_ticks / self.count as u64)
+    }
+
+    /// Returns an upper bound for a latency percentile.
+    ///
+    /// # Details
+    /// Reports the upper edge of the bucket holding the percentile,
+    /// capped at the largest recorded latency.
+    ///
+    /// # Arguments
+    /// * `percent` - Percentile from 0 to 100
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Latency bound in ticks, None if empty
+    #[allow(dead_code)]
+    pub fn percentile_ticks(&self, percent: u8) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = (self.count as u64 * percent.min(100) as u64)
+            .div_ceil(100)
+            .max(1);
+        let mut seen = 0u64;
+        for (index, &bucket) in self.buckets.iter().enumerate() {
+            seen += bucket as u64;
+            if seen >= rank {
+                return Some(((index as u64 + 1) * self.bucket_ticks).min(self.max_ticks));
+            }
+        }
+        Some(self.max_ticks)
+    }
+}
+
+impl<const N: usize> Extend<TimedEvent> for LatencyHistogram<N> {
+    /// Records every event from an event stream.
+    ///
+    /// # Arguments
+    /// * `events` - Timestamped events
+    fn extend<I: IntoIterator<Item = TimedEvent>>(&mut self, events: I) {
+        for event in events {
+            self.record_event(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::DebounceStrategy;
+
+    /// Samples a controller every 1000 ticks and collects timed events.
+    fn run(levels: &[bool], threshold: u32) -> Vec<TimedEvent> {
+        run_with(DebounceStrategy::Conservative, levels, threshold)
+    }
+
+    /// Like `run`, with a chosen debounce strategy.
+    fn run_with(strategy: DebounceStrategy, levels: &[bool], threshold: u32) -> Vec<TimedEvent> {
+        let mut ctrl = ButtonController::with_strategy(strategy, threshold);
+        let mut timer = EdgeTimer::new();
+        let mut events = Vec::new();
+        for (i, &high) in levels.iter().enumerate() {
+            let event = ctrl.update(high);
+            events.extend(timer.observe(&ctrl, event, i as u64 * 1000));
+        }
+        events
+    }
+
+    fn timed(event: ButtonEvent, edge_ticks: u64, confirmed_ticks: u64) -> TimedEvent {
+        TimedEvent {
+            event,
+            edge_ticks,
+            confirmed_ticks,
+        }
+    }
+
+    // ==================== Edge Timer Tests ====================
+
+    #[test]
+    fn test_clean_press_latency() {
+        let levels = [true, true, true, false, false, false, false];
+        let events = run(&levels, 3);
+        assert_eq!(events, [timed(ButtonEvent::Pressed, 3000, 6000)]);
+        assert_eq!(events[0].latency_ticks(), 3000);
+    }
+
+    #[test]
+    fn test_bounce_keeps_first_edge() {
+        let levels = [true, true, true, false, true, false, false, false, false];
+        let events = run(&levels, 3);
+        assert_eq!(events, [timed(ButtonEvent::Pressed, 3000, 8000)]);
+    }
+
+    #[test]
+    fn test_abandoned_glitch_forgotten() {
+        let mut levels = vec![true; 4];
+        levels.push(false);
+        levels.extend([true; 4]);
+        levels.extend([false; 4]);
+        let events = run(&levels, 3);
+        assert_eq!(events, [timed(ButtonEvent::Pressed, 9000, 12000)]);
+    }
+
+    #[test]
+    fn test_release_timed() {
+        let mut levels = vec![true; 3];
+        levels.extend([false; 4]);
+        levels.extend([true; 4]);
+        let events = run(&levels, 3);
+        assert_eq!(events[1], timed(ButtonEvent::Released, 7000, 10000));
+    }
+
+    #[test]
+    fn test_eager_press_has_zero_latency() {
+        let levels = [true, true, true, false, false, false, false];
+        let events = run_with(DebounceStrategy::Eager, &levels, 3);
+        assert_eq!(events, [timed(ButtonEvent::Pressed, 3000, 3000)]);
+    }
+
+    #[test]
+    fn test_eager_latency_excludes_lock_out() {
+        let mut levels = vec![true; 3];
+        levels.extend([false; 2]);
+        levels.extend([true; 4]);
+        let events = run_with(DebounceStrategy::Eager, &levels, 3);
+        assert_eq!(
+            events,
+            [
+                timed(ButtonEvent::Pressed, 3000, 3000),
+                timed(ButtonEvent::Released, 5000, 7000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eager_release_after_lock_out_has_zero_latency() {
+        let mut levels = vec![true; 3];
+        levels.extend([false; 6]);
+        levels.extend([true; 2]);
+        let events = run_with(DebounceStrategy::Eager, &levels, 3);
+        assert_eq!(events[1], timed(ButtonEvent::Released, 9000, 9000));
+    }
+
+    #[test]
+    fn test_event_without_edge_has_zero_latency() {
+        let ctrl = ButtonController::new();
+        let mut timer = EdgeTimer::new();
+        let event = timer.observe(&ctrl, Some(ButtonEvent::Pressed), 500);
+        assert_eq!(event.unwrap().latency_ticks(), 0);
+    }
+
+    // ==================== Histogram Tests ====================
+
+    #[test]
+    fn test_empty_histogram() {
+        let hist = LatencyHistogram::<4>::new(10);
+        assert_eq!(hist.count(), 0);
+        assert_eq!(hist.average_ticks(), None);
+        assert_eq!(hist.percentile_ticks(50), None);
+    }
+
+    #[test]
+    fn test_latencies_bucketed() {
+        let mut hist = LatencyHistogram::<4>::new(10);
+        for latency in [0, 9, 10, 25, 1000] {
+            hist.record(latency);
+        }
+        assert_eq!(hist.buckets(), &[2, 1, 1, 1]);
+        assert_eq!(hist.max_ticks(), 1000);
+        assert_eq!(hist.average_ticks(), Some(1044 / 5));
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut hist = LatencyHistogram::<8>::new(10);
+        for latency in 0..100 {
+            hist.record(latency % 50);
+        }
+        assert_eq!(hist.percentile_ticks(50), Some(30));
+        assert_eq!(hist.percentile_ticks(100), Some(49));
+        assert_eq!(hist.percentile_ticks(0), Some(10));
+    }
+
+    #[test]
+    fn test_histogram_from_event_stream() {
+        let mut levels = vec![true; 3];
+        levels.extend([false; 4]);
+        levels.extend([true; 4]);
+        let mut hist = LatencyHistogram::<4>::new(1000);
+        hist.extend(run(&levels, 3));
+        assert_eq!(hist.count(), 2);
+        assert_eq!(hist.buckets(), &[0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn test_zero_bucket_width_clamped() {
+        assert_eq!(LatencyHistogram::<2>::new(0).bucket_ticks(), 1);
+    }
+}
@@ -33,13 +33,15 @@
 //! LED State Management for RP2350.
 //!
 //! BRIEF:
-//! Provides LED state enumeration and blink controller.
+//! Provides LED state enumeration, timed blink patterns and blink controller.
+//! Patterns include Morse code text with standard or Farnsworth timing.
 //!
 //! AUTHOR: Kevin Thomas
 //! CREATION DATE: December 5, 2025
-//! UPDATE DATE: December 6, 2025
+//! UPDATE DATE: October 18, 2026
 
-use crate::config::BLINK_DELAY_MS;
+use crate::config::{BLINK_DELAY_MS, MORSE_TEXT_CAPACITY};
+use crate::morse;
 
 /// LED state enumeration.
 ///
@@ -51,12 +53,292 @@ use crate::config::BLINK_DELAY_MS;
 /// * `On` - LED is currently on (high)
 /// * `Off` - LED is currently off (low)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(dead_code)]
 pub enum LedState {
     On,
     Off,
 }
 
+/// Morse code element and gap durations.
+///
+/// # Fields
+/// * `dit_ms` - Dit length; dahs last three dits and element gaps one
+/// * `char_gap_ms` - Gap between characters
+/// * `word_gap_ms` - Gap between words and before the message repeats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct MorseTiming {
+    pub dit_ms: u32,
+    pub char_gap_ms: u32,
+    pub word_gap_ms: u32,
+}
+
+impl MorseTiming {
+    /// Returns standard timing for a speed.
+    ///
+    /// # Details
+    /// Character gaps are three dits and word gaps seven dits.
+    ///
+    /// # Arguments
+    /// * `wpm` - Words per minute
+    ///
+    /// # Returns
+    /// * `Self` - Standard Morse timing
+    #[allow(dead_code)]
+    pub fn new(wpm: u32) -> Self {
+        let dit_ms = morse::dit_ms(wpm) as u32;
+        Self {
+            dit_ms,
+            char_gap_ms: 3 * dit_ms,
+            word_gap_ms: 7 * dit_ms,
+        }
+    }
+
+    /// Returns Farnsworth timing.
+    ///
+    /// # Details
+    /// Characters are sent at `char_wpm` while character and word gaps
+    /// are stretched so the overall speed is `effective_wpm`, using the
+    /// ARRL formula. Falls back to standard timing when the effective
+    /// speed is not slower than the character speed.
+    ///
+    /// # Arguments
+    /// * `char_wpm` - Speed of the characters themselves
+    /// * `effective_wpm` - Overall speed including gaps
+    ///
+    /// # Returns
+    /// * `Self` - Farnsworth Morse timing
+    #[allow(dead_code)]
+    pub fn farnsworth(char_wpm: u32, effective_wpm: u32) -> Self {
+        let standard = Self::new(char_wpm);
+        let (c, s) = (char_wpm.max(1) as u64, effective_wpm.max(1) as u64);
+        if s >= c {
+            return standard;
+        }
+        let total_delay_ms = (60_000 * c - 37_200 * s) / (s * c);
+        Self {
+            char_gap_ms: (3 * total_delay_ms / 19) as u32,
+            word_gap_ms: (7 * total_delay_ms / 19) as u32,
+            ..standard
+        }
+    }
+}
+
+/// Fixed-capacity Morse message.
+///
+/// # Details
+/// Holds the text inline so a pattern can carry a message built at
+/// runtime, such as a counter value, without a static lifetime.
+/// Text longer than `MORSE_TEXT_CAPACITY` bytes is truncated.
+///
+/// # Fields
+/// * `bytes` - ASCII text, zero-filled past `len`
+/// * `len` - Number of bytes in use
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct MorseText {
+    bytes: [u8; MORSE_TEXT_CAPACITY],
+    len: u8,
+}
+
+impl MorseText {
+    /// Copies a message into a new buffer.
+    ///
+    /// # Arguments
+    /// * `text` - ASCII message
+    ///
+    /// # Returns
+    /// * `Self` - Message, truncated to `MORSE_TEXT_CAPACITY` bytes
+    #[allow(dead_code)]
+    pub const fn new(text: &str) -> Self {
+        let src = text.as_bytes();
+        let mut bytes = [0; MORSE_TEXT_CAPACITY];
+        let mut len = 0;
+        while len < src.len() && len < MORSE_TEXT_CAPACITY {
+            bytes[len] = src[len];
+            len += 1;
+        }
+        Self {
+            bytes,
+            len: len as u8,
+        }
+    }
+
+    /// Returns the message text.
+    ///
+    /// # Returns
+    /// * `&[u8]` - Bytes in use
+    #[allow(dead_code)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+/// Walks the on/off segments of a Morse message.
+///
+/// # Details
+/// Characters without a Morse code are skipped. A trailing word gap
+/// separates repetitions. Stops early when `segment` returns false.
+///
+/// # Arguments
+/// * `text` - ASCII message
+/// * `timing` - Element and gap durations
+/// * `segment` - Called with each LED state and its duration
+fn for_each_morse_segment(
+    text: &MorseText,
+    timing: &MorseTiming,
+    mut segment: impl FnMut(LedState, u64) -> bool,
+) {
+    let dit = timing.dit_ms as u64;
+    let mut gap = None;
+    for c in text.as_bytes().iter().map(|&b| b as char) {
+        if c == ' ' {
+            gap = gap.map(|_| timing.word_gap_ms as u64);
+            continue;
+        }
+        let Some(code) = morse::encode(c) else {
+            continue;
+        };
+        if let Some(gap) = gap {
+            if !segment(LedState::Off, gap) {
+                return;
+            }
+        }
+        for (i, element) in code.bytes().enumerate() {
+            if i > 0 && !segment(LedState::Off, dit) {
+                return;
+            }
+            let on = if element == b'-' { 3 * dit } else { dit };
+            if !segment(LedState::On, on) {
+                return;
+            }
+        }
+        gap = Some(timing.char_gap_ms as u64);
+    }
+    if gap.is_some() {
+        segment(LedState::Off, timing.word_gap_ms as u64);
+    }
+}
+
+/// Timed LED pattern.
+///
+/// # Details
+/// Describes a repeating on/off sequence evaluated against elapsed time.
+/// Used to signal conditions such as faults without blocking.
+///
+/// # Variants
+/// * `Solid` - Constant LED state
+/// * `Blink` - Repeating on/off cycle
+/// * `Burst` - `count` on/off pulses followed by a pause
+/// * `Morse` - `text` keyed as Morse code, repeating after a word gap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum LedPattern {
+    Solid(LedState),
+    Blink {
+        on_ms: u32,
+        off_ms: u32,
+    },
+    Burst {
+        count: u8,
+        on_ms: u32,
+        off_ms: u32,
+        pause_ms: u32,
+    },
+    Morse {
+        text: MorseText,
+        timing: MorseTiming,
+    },
+}
+
+impl LedPattern {
+    /// Returns pattern repetition period.
+    ///
+    /// # Details
+    /// Solid patterns have a period of zero.
+    ///
+    /// # Returns
+    /// * `u64` - Period in milliseconds
+    #[allow(dead_code)]
+    pub fn period_ms(&self) -> u64 {
+        match *self {
+            LedPattern::Solid(_) => 0,
+            LedPattern::Blink { on_ms, off_ms } => on_ms as u64 + off_ms as u64,
+            LedPattern::Burst {
+                count,
+                on_ms,
+                off_ms,
+                pause_ms,
+            } => count as u64 * (on_ms as u64 + off_ms as u64) + pause_ms as u64,
+            LedPattern::Morse { text, timing } => {
+                let mut period = 0;
+                for_each_morse_segment(&text, &timing, |_, duration| {
+                    period += duration;
+                    true
+                });
+                period
+            }
+        }
+    }
+
+    /// Returns LED state at a point in the pattern.
+    ///
+    /// # Details
+    /// Elapsed time wraps at the pattern period.
+    /// Patterns with a zero period stay off unless solid on.
+    ///
+    /// # Arguments
+    /// * `elapsed_ms` - Time since the pattern started in milliseconds
+    ///
+    /// # Returns
+    /// * `LedState` - LED state at that time
+    #[allow(dead_code)]
+    pub fn state_at(&self, elapsed_ms: u64) -> LedState {
+        let (count, on_ms, off_ms) = match *self {
+            LedPattern::Solid(state) => return state,
+            LedPattern::Blink { on_ms, off_ms } => (1, on_ms as u64, off_ms as u64),
+            LedPattern::Burst {
+                count,
+                on_ms,
+                off_ms,
+                ..
+            } => (count as u64, on_ms as u64, off_ms as u64),
+            LedPattern::Morse { text, timing } => {
+                let period = self.period_ms();
+                if period == 0 {
+                    return LedState::Off;
+                }
+                let mut t = elapsed_ms % period;
+                let mut state = LedState::Off;
+                for_each_morse_segment(&text, &timing, |segment, duration| {
+                    state = segment;
+                    if t < duration {
+                        return false;
+                    }
+                    t -= duration;
+                    true
+                });
+                return state;
+            }
+        };
+        let period = self.period_ms();
+        if period == 0 || on_ms == 0 {
+            return LedState::Off;
+        }
+        let t = elapsed_ms % period;
+        let pulse = on_ms + off_ms;
+        if t < count * pulse && t % pulse < on_ms {
+            LedState::On
+        } else {
+            LedState::Off
+        }
+    }
+}
+
 /// LED controller with state tracking.
 ///
 /// # Details
@@ -67,6 +349,7 @@ pub enum LedState {
 /// * `state` - Current LED state
 /// * `delay_ms` - Blink delay in milliseconds
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(dead_code)]
 pub struct LedController {
     state: LedState,
@@ -97,9 +380,21 @@ impl LedController {
     /// * `Self` - New LedController instance
     #[allow(dead_code)]
     pub fn new() -> Self {
+        Self::with_delay_ms(BLINK_DELAY_MS)
+    }
+
+    /// Creates new LED controller with a given blink delay.
+    ///
+    /// # Arguments
+    /// * `delay_ms` - Blink delay in milliseconds
+    ///
+    /// # Returns
+    /// * `Self` - New LedController instance
+    #[allow(dead_code)]
+    pub fn with_delay_ms(delay_ms: u64) -> Self {
         Self {
             state: LedState::Off,
-            delay_ms: BLINK_DELAY_MS,
+            delay_ms,
         }
     }
 
@@ -116,6 +411,25 @@ impl LedController {
             LedState::On => LedState::Off,
             LedState::Off => LedState::On,
         };
+        trace!("led: {}", self.state);
+        self.state
+    }
+
+    /// Plays a pattern and returns the resulting LED state.
+    ///
+    /// # Details
+    /// Evaluates the pattern, including Morse messages, at the elapsed
+    /// time and records the result as the current state.
+    ///
+    /// # Arguments
+    /// * `pattern` - Pattern being played
+    /// * `elapsed_ms` - Time since the pattern started in milliseconds
+    ///
+    /// # Returns
+    /// * `LedState` - LED state to drive
+    #[allow(dead_code)]
+    pub fn play(&mut self, pattern: &LedPattern, elapsed_ms: u64) -> LedState {
+        self.state = pattern.state_at(elapsed_ms);
         self.state
     }
 
@@ -177,14 +491,172 @@ mod tests {
         assert!(!led_state_to_level(LedState::Off));
     }
 
+    // ==================== LedPattern Tests ====================
+
+    #[test]
+    fn test_solid_pattern() {
+        let on = LedPattern::Solid(LedState::On);
+        assert_eq!(on.state_at(0), LedState::On);
+        assert_eq!(on.state_at(123_456), LedState::On);
+        assert_eq!(on.period_ms(), 0);
+    }
+
+    #[test]
+    fn test_blink_pattern() {
+        let blink = LedPattern::Blink {
+            on_ms: 100,
+            off_ms: 300,
+        };
+        assert_eq!(blink.period_ms(), 400);
+        assert_eq!(blink.state_at(0), LedState::On);
+        assert_eq!(blink.state_at(99), LedState::On);
+        assert_eq!(blink.state_at(100), LedState::Off);
+        assert_eq!(blink.state_at(399), LedState::Off);
+        assert_eq!(blink.state_at(400), LedState::On);
+    }
+
+    #[test]
+    fn test_burst_pattern() {
+        let burst = LedPattern::Burst {
+            count: 2,
+            on_ms: 100,
+            off_ms: 100,
+            pause_ms: 600,
+        };
+        assert_eq!(burst.period_ms(), 1000);
+        assert_eq!(burst.state_at(50), LedState::On);
+        assert_eq!(burst.state_at(150), LedState::Off);
+        assert_eq!(burst.state_at(250), LedState::On);
+        assert_eq!(burst.state_at(350), LedState::Off);
+        assert_eq!(burst.state_at(500), LedState::Off);
+        assert_eq!(burst.state_at(1050), LedState::On);
+    }
+
+    #[test]
+    fn test_zero_period_pattern_off() {
+        let blink = LedPattern::Blink {
+            on_ms: 0,
+            off_ms: 0,
+        };
+        assert_eq!(blink.state_at(10), LedState::Off);
+    }
+
+    // ==================== Morse Pattern Tests ====================
+
+    fn morse(text: &str) -> LedPattern {
+        LedPattern::Morse {
+            text: MorseText::new(text),
+            timing: MorseTiming::new(20),
+        }
+    }
+
+    #[test]
+    fn test_standard_timing() {
+        let timing = MorseTiming::new(20);
+        assert_eq!(timing.dit_ms, 60);
+        assert_eq!(timing.char_gap_ms, 180);
+        assert_eq!(timing.word_gap_ms, 420);
+    }
+
+    #[test]
+    fn test_paris_is_fifty_units() {
+        assert_eq!(morse("PARIS").period_ms(), 50 * 60);
+    }
+
+    #[test]
+    fn test_morse_letter_a_segments() {
+        let a = morse("a");
+        assert_eq!(a.state_at(0), LedState::On);
+        assert_eq!(a.state_at(59), LedState::On);
+        assert_eq!(a.state_at(60), LedState::Off);
+        assert_eq!(a.state_at(120), LedState::On);
+        assert_eq!(a.state_at(299), LedState::On);
+        assert_eq!(a.state_at(300), LedState::Off);
+        assert_eq!(a.state_at(a.period_ms()), LedState::On);
+    }
+
+    #[test]
+    fn test_morse_word_gap_between_words() {
+        let e_e = morse("E E");
+        assert_eq!(e_e.period_ms(), 60 + 420 + 60 + 420);
+        assert_eq!(e_e.state_at(479), LedState::Off);
+        assert_eq!(e_e.state_at(480), LedState::On);
+    }
+
+    #[test]
+    fn test_morse_skips_unsupported_characters() {
+        assert_eq!(morse("E~").period_ms(), morse("E").period_ms());
+        assert_eq!(morse(" ~ ").period_ms(), 0);
+        assert_eq!(morse("").state_at(100), LedState::Off);
+    }
+
+    #[test]
+    fn test_farnsworth_stretches_gaps_only() {
+        let timing = MorseTiming::farnsworth(18, 5);
+        let standard = MorseTiming::new(18);
+        assert_eq!(timing.dit_ms, standard.dit_ms);
+        assert!(timing.char_gap_ms > standard.char_gap_ms);
+        assert!(timing.word_gap_ms > standard.word_gap_ms);
+        assert!(timing.word_gap_ms.abs_diff(7 * timing.char_gap_ms / 3) <= 2);
+    }
+
+    #[test]
+    fn test_farnsworth_effective_speed() {
+        let pattern = LedPattern::Morse {
+            text: MorseText::new("PARIS"),
+            timing: MorseTiming::farnsworth(18, 5),
+        };
+        let minute_ms = pattern.period_ms() * 5;
+        assert!((59_000..=61_000).contains(&minute_ms), "{minute_ms}");
+    }
+
+    #[test]
+    fn test_morse_runtime_text() {
+        let count = std::hint::black_box(42u32);
+        let text = format!("N{count}");
+        let pattern = morse(&text);
+        drop(text);
+        assert_eq!(pattern, morse("N42"));
+        assert_eq!(pattern.period_ms(), morse("N42").period_ms());
+    }
+
+    #[test]
+    fn test_morse_text_truncated_to_capacity() {
+        let long = "E".repeat(MORSE_TEXT_CAPACITY + 8);
+        let text = MorseText::new(&long);
+        assert_eq!(text.as_bytes().len(), MORSE_TEXT_CAPACITY);
+        assert_eq!(MorseText::new("").as_bytes(), b"");
+    }
+
+    #[test]
+    fn test_farnsworth_not_slower_is_standard() {
+        assert_eq!(MorseTiming::farnsworth(15, 15), MorseTiming::new(15));
+        assert_eq!(MorseTiming::farnsworth(15, 25), MorseTiming::new(15));
+    }
+
     // ==================== LedController Tests ====================
 
+    #[test]
+    fn test_play_tracks_pattern_state() {
+        let mut ctrl = LedController::new();
+        let pattern = morse("T");
+        assert_eq!(ctrl.play(&pattern, 0), LedState::On);
+        assert_eq!(ctrl.play(&pattern, 180), LedState::Off);
+        assert_eq!(ctrl.toggle(), LedState::On);
+    }
+
     #[test]
     fn test_new_controller() {
         let ctrl = LedController::new();
         assert_eq!(ctrl.delay_ms(), BLINK_DELAY_MS);
     }
 
+    #[test]
+    fn test_controller_with_delay() {
+        let ctrl = LedController::with_delay_ms(250);
+        assert_eq!(ctrl.delay_ms(), 250);
+    }
+
     #[test]
     fn test_default_equals_new() {
         let default = LedController::default();
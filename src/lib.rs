@@ -38,10 +38,32 @@
 //!
 //! AUTHOR: Kevin Thomas
 //! CREATION DATE: November 28, 2025
-//! UPDATE DATE: December 5, 2025
+//! UPDATE DATE: October 18, 2026
 
 #![cfg_attr(not(test), no_std)]
 
+#[macro_use]
+mod log;
+
+pub mod bininfo;
+pub mod board;
+pub mod bootsel;
 pub mod button;
 pub mod config;
+pub mod encoder;
+pub mod fault;
+pub mod keypad;
+pub mod ladder;
+pub mod latency;
 pub mod led;
+pub mod morse;
+pub mod panic;
+pub mod power;
+pub mod queue;
+pub mod ring;
+pub mod sequence;
+pub mod settings;
+pub mod stats;
+pub mod touch;
+pub mod vertical;
+pub mod watchdog;
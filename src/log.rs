@@ -0,0 +1,119 @@
+/*
+ * @file log.rs
+ * @brief Feature-gated logging macros
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: log.rs
+//!
+//! DESCRIPTION:
+//! Feature-Gated Logging Macros for RP2350.
+//!
+//! BRIEF:
+//! Forwards `trace!`, `debug!`, `info!`, `warn!` and `error!` to defmt
+//! when the `defmt` feature is enabled. Without the feature the macros
+//! only borrow their arguments so host builds stay warning-free.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+#![allow(unused_macros)]
+
+/// Emits a trace-level log message.
+///
+/// # Details
+/// Forwards to `defmt::trace!` when the `defmt` feature is enabled.
+macro_rules! trace {
+    ($s:literal $(, $x:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::trace!($s $(, $x)*);
+        #[cfg(not(feature = "defmt"))]
+        {
+            $(let _ = &$x;)*
+        }
+    }};
+}
+
+/// Emits a debug-level log message.
+///
+/// # Details
+/// Forwards to `defmt::debug!` when the `defmt` feature is enabled.
+macro_rules! debug {
+    ($s:literal $(, $x:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::debug!($s $(, $x)*);
+        #[cfg(not(feature = "defmt"))]
+        {
+            $(let _ = &$x;)*
+        }
+    }};
+}
+
+/// Emits an info-level log message.
+///
+/// # Details
+/// Forwards to `defmt::info!` when the `defmt` feature is enabled.
+macro_rules! info {
+    ($s:literal $(, $x:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::info!($s $(, $x)*);
+        #[cfg(not(feature = "defmt"))]
+        {
+            $(let _ = &$x;)*
+        }
+    }};
+}
+
+/// Emits a warning-level log message.
+///
+/// # Details
+/// Forwards to `defmt::warn!` when the `defmt` feature is enabled.
+macro_rules! warn {
+    ($s:literal $(, $x:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::warn!($s $(, $x)*);
+        #[cfg(not(feature = "defmt"))]
+        {
+            $(let _ = &$x;)*
+        }
+    }};
+}
+
+/// Emits an error-level log message.
+///
+/// # Details
+/// Forwards to `defmt::error!` when the `defmt` feature is enabled.
+macro_rules! error {
+    ($s:literal $(, $x:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::error!($s $(, $x)*);
+        #[cfg(not(feature = "defmt"))]
+        {
+            $(let _ = &$x;)*
+        }
+    }};
+}
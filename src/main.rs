@@ -34,58 +34,897 @@
 //!
 //! BRIEF:
 //! Main application entry point for RP2350 GPIO button driver using Embassy.
-//! Implements button input on GPIO 15 controlling LED on GPIO 16.
-//! Button is active-low (tied to GND when pressed).
+//! Implements button input controlling an LED on the GPIOs of the
+//! selected board profile (GPIO 15 and 16 on the Pico 2). Button and
+//! LED polarity also come from the profile.
+//! Settings and press counters persist in the reserved flash sectors.
+//! Button and LED run as separate tasks; the watchdog is fed only while
+//! both report liveness, and watchdog resets are reported at boot.
+//! After an idle timeout the LED turns off and the core idles in WFE
+//! sleep until the next press; clocks stay on (not dormant).
+//! Holding the button for 10 s at power-up reboots into BOOTSEL.
+//! With the `defmt` feature, logs over RTT and panics via panic-probe.
+//! With the `panic-led` feature, panics blink the LED and are reported
+//! at the next boot.
+//! With the `pio-debounce` feature, PIO0 samples an extra input bank
+//! that is debounced by a vertical counter. With the `touch` feature,
+//! a capacitive pad is measured by GPIO charge timing.
 //!
 //! AUTHOR: Kevin Thomas
 //! CREATION DATE: November 28, 2025
-//! UPDATE DATE: December 5, 2025
+//! UPDATE DATE: October 18, 2026
 
 #![no_std]
 #![no_main]
 
+#[macro_use]
+mod log;
+
+mod bininfo;
+mod board;
+mod bootsel;
 mod button;
 mod config;
+mod fault;
+mod latency;
 mod led;
+mod morse;
+#[cfg(feature = "panic-led")]
+mod panic;
+mod power;
+#[cfg(feature = "multicore")]
+mod queue;
+mod settings;
+mod stats;
+#[cfg(feature = "touch")]
+mod touch;
+#[cfg(feature = "pio-debounce")]
+mod vertical;
+mod watchdog;
 
-use button::ButtonController;
-use config::DEBOUNCE_DELAY_MS;
+use bininfo::{pin_mask, EntryPtr, PinsWithName};
+use board::{pin_number, BOARD};
+use bootsel::{BootselConfig, BootselDetector, REBOOT_NO_RETURN_ON_SUCCESS, REBOOT_TYPE_BOOTSEL};
+use button::{ButtonController, ButtonEvent, InputConfig};
+use config::{
+    BUTTON_PIN, DEBOUNCE_STRATEGY, FLASH_SIZE_BYTES, LATENCY_BUCKETS, LATENCY_BUCKET_US, LED_PIN,
+    LED_TICK_MS, RESET_INDICATION_MS, SETTINGS_FLASH_OFFSET, SETTINGS_SAVE_INTERVAL_MS,
+    SETTINGS_SECTOR_COUNT, WATCHDOG_FEED_MS, WATCHDOG_TIMEOUT_MS,
+};
+#[cfg(feature = "panic-led")]
+use core::mem::MaybeUninit;
+#[cfg(feature = "panic-led")]
+use core::ptr::addr_of_mut;
+#[cfg(feature = "defmt")]
+use defmt_rtt as _;
 use embassy_executor::Spawner;
+use embassy_rp::flash::{self, Blocking, Flash};
 use embassy_rp::gpio::{Input, Level, Output, Pull};
-use embassy_time::Timer;
-use led::{led_state_to_level, LedState};
+use embassy_rp::peripherals::FLASH;
+use embassy_rp::watchdog::{ResetReason, Watchdog};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{Duration, Instant, Timer};
+use latency::{EdgeTimer, LatencyHistogram};
+use led::{led_state_to_level, LedController, LedPattern, LedState};
+#[cfg(feature = "panic-led")]
+use panic::{PanicRecord, PANIC_PATTERN};
+#[cfg(not(any(feature = "defmt", feature = "panic-led")))]
 use panic_halt as _;
+#[cfg(all(feature = "defmt", not(feature = "panic-led")))]
+use panic_probe as _;
+#[cfg(not(feature = "multicore"))]
+use power::{IdlePolicy, IdleTimer};
+use settings::{Settings, SettingsFlash, SettingsStore};
+use stats::TrackedButton;
+use watchdog::{Liveness, ResetCause, BUTTON_TASK, LED_TASK};
+#[cfg(feature = "pio-debounce")]
+use {
+    config::{
+        pio_clock_divider_bits, DEBOUNCE_COUNT, PIO_INPUT_BASE_PIN, PIO_INPUT_COUNT,
+        PIO_SAMPLE_LOOPS,
+    },
+    embassy_rp::bind_interrupts,
+    embassy_rp::clocks::clk_sys_freq,
+    embassy_rp::peripherals::PIO0,
+    embassy_rp::pio::{
+        Common, Config as PioConfig, InterruptHandler, Pin as PioPin, Pio, ShiftConfig,
+        ShiftDirection, StateMachine,
+    },
+    fixed::types::U24F8,
+    vertical::VerticalDebouncer,
+};
+#[cfg(feature = "multicore")]
+use {
+    config::{CORE1_STACK_BYTES, INPUT_QUEUE_LEN},
+    embassy_executor::Executor,
+    embassy_rp::multicore::{spawn_core1, Stack},
+    latency::TimedEvent,
+    queue::{Consumer, Producer, SpscQueue},
+    static_cell::StaticCell,
+    stats::ButtonStats,
+};
+#[cfg(feature = "touch")]
+use {
+    config::{TOUCH_DISCHARGE_US, TOUCH_MAX_COUNT, TOUCH_PIN},
+    embassy_rp::gpio::Flex,
+    touch::TouchSensor,
+};
+
+/// Takes a GPIO from the peripherals, checked against a configured pin.
+///
+/// # Details
+/// The GPIO number is parsed from the peripheral name at compile time
+/// and must equal the configured pin, so the firmware cannot drive a
+/// different pin than the board profile describes.
+macro_rules! board_pin {
+    ($p:ident . $pin:ident, $configured:expr) => {{
+        const _: () = assert!(
+        matches!(pin_number(stringify!($pin)), Some(n) if n == $configured),
+            concat!(stringify!($pin), " does not match the board profile")
+        );
+        $p.$pin
+    }};
+}
+
+// Defines `file_board_pins!` for a `[board]` section in `button.toml`.
+include!(concat!(env!("OUT_DIR"), "/board_pins.rs"));
+
+/// Takes the button and LED pins of the selected board profile.
+///
+/// # Returns
+/// * `(button, led)` - Button and LED pin peripherals
+macro_rules! board_pins {
+    ($p:ident) => {{
+        #[cfg(button_toml_board)]
+        let pins = file_board_pins!($p);
+        #[cfg(not(any(button_toml_board, feature = "board-carrier")))]
+        let pins = (
+            board_pin!($p.PIN_15, BUTTON_PIN),
+            board_pin!($p.PIN_16, LED_PIN),
+        );
+        #[cfg(all(feature = "board-carrier", not(button_toml_board)))]
+        let pins = (
+            board_pin!($p.PIN_20, BUTTON_PIN),
+            board_pin!($p.PIN_21, LED_PIN),
+        );
+        pins
+    }};
+}
+
+/// Program metadata shown by `picotool info`.
+#[link_section = ".bi_entries"]
+#[used]
+static PICOTOOL_ENTRIES: [embassy_rp::binary_info::EntryAddr; 4] = [
+    embassy_rp::binary_info::rp_cargo_bin_name!(),
+    embassy_rp::binary_info::rp_cargo_version!(),
+    embassy_rp::binary_info::rp_program_description!(c"Debounced button driver with LED feedback"),
+    embassy_rp::binary_info::rp_program_build_attribute!(),
+];
+
+/// Button pin name shown by `picotool info`.
+static BUTTON_PIN_INFO: PinsWithName = PinsWithName::new(pin_mask(BUTTON_PIN), c"Button");
+
+/// LED pin name shown by `picotool info`.
+static LED_PIN_INFO: PinsWithName = PinsWithName::new(pin_mask(LED_PIN), c"LED");
+
+/// Pin assignments shown by `picotool info`.
+#[link_section = ".bi_entries"]
+#[used]
+static PICOTOOL_PIN_ENTRIES: [EntryPtr; 2] = [BUTTON_PIN_INFO.addr(), LED_PIN_INFO.addr()];
+
+/// Publishes each enabled cargo feature as a picotool program feature.
+macro_rules! bi_features {
+    ($($feature:literal),* $(,)?) => {$(
+        #[cfg(feature = $feature)]
+        const _: () = {
+            #[link_section = ".bi_entries"]
+            #[used]
+            static ENTRY: embassy_rp::binary_info::EntryAddr = embassy_rp::binary_info::str!(
+                embassy_rp::binary_info::consts::TAG_RASPBERRY_PI,
+                embassy_rp::binary_info::consts::ID_RP_PROGRAM_FEATURE,
+                match core::ffi::CStr::from_bytes_with_nul(concat!($feature, "\0").as_bytes()) {
+                    Ok(name) => name,
+                    Err(_) => panic!("feature name contains a null byte"),
+                }
+            );
+        };
+    )*};
+}
+
+bi_features!(
+    "defmt",
+    "panic-led",
+    "pio-debounce",
+    "multicore",
+    "touch",
+    "board-pico2",
+    "board-carrier",
+);
+
+/// Button pad configuration from the board profile.
+const BUTTON_INPUT: InputConfig = match BOARD.input_config() {
+    Ok(config) => config,
+    Err(_) => panic!("button input configuration is invalid"),
+};
+
+/// Pad configuration for the active-low PIO debounce bank.
+#[cfg(feature = "pio-debounce")]
+const PIO_INPUT: InputConfig = InputConfig::for_polarity(board::Polarity::ActiveLow);
+
+/// Liveness reports from the button and LED tasks.
+static LIVENESS: Liveness = Liveness::new(BUTTON_TASK | LED_TASK);
+
+/// LED pattern requested by the button task.
+static LED_PATTERN: Signal<CriticalSectionRawMutex, LedPattern> = Signal::new();
+
+/// Debounced input event reported by core 1.
+///
+/// # Fields
+/// * `timed` - Timestamped press or release
+/// * `stats` - Button statistics including this event
+#[cfg(feature = "multicore")]
+#[derive(Clone, Copy)]
+struct InputReport {
+    timed: TimedEvent,
+    stats: ButtonStats,
+}
+
+/// Debounced input events passed from core 1 to the application.
+#[cfg(feature = "multicore")]
+static INPUT_EVENTS: SpscQueue<InputReport, INPUT_QUEUE_LEN> = SpscQueue::new();
+
+/// Wakes the application when core 1 queues an input event.
+#[cfg(feature = "multicore")]
+static INPUT_READY: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+/// Stack for the core 1 input executor.
+#[cfg(feature = "multicore")]
+static mut CORE1_STACK: Stack<CORE1_STACK_BYTES> = Stack::new();
+
+/// Executor running the input task on core 1.
+#[cfg(feature = "multicore")]
+static CORE1_EXECUTOR: StaticCell<Executor> = StaticCell::new();
+
+#[cfg(feature = "pio-debounce")]
+bind_interrupts!(struct Irqs {
+    PIO0_IRQ_0 => InterruptHandler<PIO0>;
+});
+
+/// Panic record preserved in no-init RAM across the watchdog reset.
+#[cfg(feature = "panic-led")]
+#[link_section = ".uninit.PANIC_RECORD"]
+static mut PANIC_RECORD: MaybeUninit<PanicRecord> = MaybeUninit::uninit();
+
+/// RP2350 flash adapter for the settings store.
+///
+/// # Details
+/// Wraps the Embassy blocking flash driver behind `SettingsFlash`.
+struct BoardFlash<'d>(Flash<'d, FLASH, Blocking, FLASH_SIZE_BYTES>);
+
+impl SettingsFlash for BoardFlash<'_> {
+    type Error = flash::Error;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.blocking_read(offset, bytes)
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.0.blocking_write(offset, bytes)
+    }
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        self.0.blocking_erase(from, to)
+    }
+}
+
+/// Panic handler that records the panic and blinks the LED.
+///
+/// # Details
+/// Stores the location and message in `PANIC_RECORD`, then plays
+/// `PANIC_PATTERN` on the LED by busy-waiting. The watchdog is no
+/// longer fed, so it resets the chip and the record is reported at
+/// the next boot.
+///
+/// # Arguments
+/// * `info` - Panic information
+///
+/// # Returns
+/// * `!` - Never returns
+#[cfg(feature = "panic-led")]
+#[panic_handler]
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    cortex_m::interrupt::disable();
+    let (file, line, column) = info
+        .location()
+        .map_or(("", 0, 0), |l| (l.file(), l.line(), l.column()));
+    // SAFETY: interrupts are disabled and only this core runs, so
+    // nothing else accesses the record; every bit pattern is valid.
+    let record = unsafe { (*addr_of_mut!(PANIC_RECORD)).assume_init_mut() };
+    record.capture(file, line, column, info.message());
+    // SAFETY: the executor no longer runs, so the LED task cannot
+    // touch the pin while it is reconfigured here.
+    let p = unsafe { embassy_rp::Peripherals::steal() };
+    let (_, led_pin) = board_pins!(p);
+    let mut led = Output::new(led_pin, Level::from(BOARD.led_polarity.level(false)));
+    let tick_cycles = embassy_rp::clocks::clk_sys_freq() / 1000 * LED_TICK_MS as u32;
+    let mut elapsed_ms = 0;
+    loop {
+        let on = led_state_to_level(PANIC_PATTERN.state_at(elapsed_ms));
+        led.set_level(Level::from(BOARD.led_polarity.level(on)));
+        cortex_m::asm::delay(tick_cycles);
+        elapsed_ms = (elapsed_ms + LED_TICK_MS) % PANIC_PATTERN.period_ms();
+    }
+}
+
+/// Logs and clears a panic captured before the last reset.
+#[cfg(feature = "panic-led")]
+fn report_previous_panic() {
+    // SAFETY: called once at boot before any task runs; every bit
+    // pattern is a valid record and `take` validates the contents.
+    let record = unsafe { (*addr_of_mut!(PANIC_RECORD)).assume_init_mut() };
+    if let Some(record) = record.take() {
+        error!(
+            "previous panic at {}:{}:{}: {}",
+            record.file(),
+            record.line(),
+            record.column(),
+            record.message()
+        );
+    }
+}
+
+/// Reboots into the ROM USB bootloader.
+///
+/// # Details
+/// Uses the RP2350 bootrom reboot call with the BOOTSEL boot type.
+/// Spins in case the ROM returns.
+///
+/// # Returns
+/// * `!` - Never returns
+fn reboot_to_bootsel() -> ! {
+    // SAFETY: the ROM reboot call takes plain integer arguments and
+    // resets the chip; no Rust state is relied on afterwards.
+    unsafe {
+        embassy_rp::rom_data::reboot(REBOOT_TYPE_BOOTSEL | REBOOT_NO_RETURN_ON_SUCCESS, 10, 0, 0);
+    }
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
+
+/// Converts a board pull setting to the Embassy pull.
+///
+/// # Arguments
+/// * `pull` - Board profile pull
+///
+/// # Returns
+/// * `Pull` - Embassy GPIO pull
+fn gpio_pull(pull: board::Pull) -> Pull {
+    match pull {
+        board::Pull::Up => Pull::Up,
+        board::Pull::Down => Pull::Down,
+        board::Pull::None => Pull::None,
+    }
+}
+
+/// Applies hysteresis and synchronizer settings to the button pad.
+///
+/// # Details
+/// The pull is set when the input is created. Bypassing the input
+/// synchronizer has no Embassy API, so the SYSCFG bit for the pin is
+/// set directly.
+///
+/// # Arguments
+/// * `button` - Button input pin
+/// * `config` - Pad configuration
+fn apply_input_config(button: &mut Input<'static>, config: &InputConfig) {
+    button.set_schmitt(config.schmitt);
+    if config.bypass_sync {
+        embassy_rp::pac::SYSCFG
+            .proc_in_sync_bypass()
+            .modify(|w| w.0 |= 1 << BUTTON_PIN);
+    }
+}
+
+/// Selects the LED pattern for the button state.
+///
+/// # Details
+/// A BOOTSEL countdown takes priority over a wiring fault, which takes
+/// priority over the pressed state.
+///
+/// # Arguments
+/// * `bootsel` - BOOTSEL gesture detector
+/// * `button` - Tracked button
+/// * `pressed_state` - LED state for the last press or release
+/// * `settings` - Settings holding the fault pattern timing
+/// * `now_ms` - Current timestamp in milliseconds
+///
+/// # Returns
+/// * `LedPattern` - Pattern to play
+fn button_led_pattern(
+    bootsel: &BootselDetector,
+    button: &TrackedButton,
+    pressed_state: LedState,
+    settings: &Settings,
+    now_ms: u64,
+) -> LedPattern {
+    match (bootsel.led_pattern(now_ms), button.fault()) {
+        (Some(countdown), _) => countdown,
+        (None, Some(fault)) => {
+            fault.led_pattern_timed(settings.fault_blink_ms, settings.fault_pause_ms)
+        }
+        (None, None) => LedPattern::Solid(pressed_state),
+    }
+}
+
+/// Waits for the button pin to reach its pressed level.
+///
+/// # Arguments
+/// * `button` - Button input pin
+#[cfg(not(feature = "multicore"))]
+async fn wait_for_press(button: &mut Input<'static>) {
+    match BOARD.button_polarity {
+        board::Polarity::ActiveLow => button.wait_for_low().await,
+        board::Polarity::ActiveHigh => button.wait_for_high().await,
+    }
+}
+
+/// Button polling task.
+///
+/// # Details
+/// Samples the button, tracks faults and statistics, and requests the
+/// matching LED pattern. Counters are saved to flash on release, at
+/// most once per interval. Reports liveness every sample.
+/// Each sample's event is dispatched before the idle check, so an
+/// event on the last sample before the timeout is never lost. After
+/// the idle timeout, turns the LED off and waits on the button's GPIO
+/// edge interrupt; with no other work the executor idles the core in
+/// WFE sleep. Clocks and peripherals stay on, so this is not the
+/// RP2350 dormant state. The wake press is reported as a confirmed
+/// press on the next iteration, without waiting a sample interval.
+/// A BOOTSEL hold at power-up shows a countdown and reboots on release.
+/// Debounce latency from first edge to confirmation is collected into
+/// a histogram and logged on release.
+///
+/// # Arguments
+/// * `button` - Button input pin, polarity from `BOARD`
+/// * `store` - Flash settings store
+/// * `saved` - Settings loaded at boot
+#[cfg(not(feature = "multicore"))]
+#[embassy_executor::task]
+async fn button_task(
+    mut button: Input<'static>,
+    mut store: SettingsStore<BoardFlash<'static>>,
+    mut saved: Settings,
+) {
+    let controller = ButtonController::with_strategy(DEBOUNCE_STRATEGY, saved.debounce_count);
+    let mut button_state = TrackedButton::from_parts(controller, saved.stats());
+    let mut idle = IdleTimer::new(IdlePolicy::default(), Instant::now().as_millis());
+    let mut bootsel = BootselDetector::new(BootselConfig::default(), Instant::now().as_millis());
+    let mut edge_timer = EdgeTimer::new();
+    let mut latency = LatencyHistogram::<LATENCY_BUCKETS>::new(
+        Duration::from_micros(LATENCY_BUCKET_US).as_ticks(),
+    );
+    let mut last_save_ms = 0;
+    let mut pressed_state = LedState::Off;
+    let mut current_pattern = None;
+    let mut wake_event = None;
+    loop {
+        let now = Instant::now();
+        let now_ms = now.as_millis();
+        let event = match wake_event.take() {
+            Some(event) => Some(event),
+            None => {
+                let level = BOARD.button_polarity.to_active_low(button.is_high());
+                button_state.update(level, now_ms)
+            }
+        };
+        if let Some(timed) = edge_timer.observe(button_state.controller(), event, now.as_ticks()) {
+            latency.record_event(&timed);
+        }
+        if bootsel.update(event, now_ms) {
+            info!("rebooting into BOOTSEL");
+            reboot_to_bootsel();
+        }
+        if let Some(event) = event {
+            idle.record(event, now_ms);
+            pressed_state = match event {
+                ButtonEvent::Pressed => LedState::On,
+                ButtonEvent::Released => {
+                    debug!("stats: {}", button_state.stats());
+                    debug!("latency: {}", latency);
+                    let current = saved.with_stats(button_state.stats());
+                    if current != saved
+                        && now_ms - last_save_ms >= SETTINGS_SAVE_INTERVAL_MS
+                        && store.save(&current).is_ok()
+                    {
+                        saved = current;
+                        last_save_ms = now_ms;
+                    }
+                    LedState::Off
+                }
+            };
+        }
+        let pattern = button_led_pattern(&bootsel, &button_state, pressed_state, &saved, now_ms);
+        if current_pattern != Some(pattern) {
+            debug!("led: {}", pattern);
+            current_pattern = Some(pattern);
+            LED_PATTERN.signal(pattern);
+        }
+        LIVENESS.report(BUTTON_TASK);
+        if idle.should_sleep(button_state.controller().is_pressed(), now_ms) {
+            info!("idle, waiting for button press");
+            LED_PATTERN.signal(LedPattern::Solid(LedState::Off));
+            LIVENESS.park(BUTTON_TASK);
+            wait_for_press(&mut button).await;
+            LIVENESS.unpark(BUTTON_TASK);
+            let wake_ms = Instant::now().as_millis();
+            idle.wake(wake_ms);
+            wake_event = button_state.wake_pressed(wake_ms);
+            current_pattern = None;
+            continue;
+        }
+        Timer::after_millis(saved.debounce_delay_ms as u64).await;
+    }
+}
+
+/// LED output task.
+///
+/// # Details
+/// Plays the most recently requested pattern, including Morse messages,
+/// restarting it whenever a new pattern arrives. Reports liveness every
+/// tick while blinking and parks while a solid pattern waits for the
+/// next request.
+///
+/// # Arguments
+/// * `led` - LED output pin
+/// * `tick_ms` - Pattern update interval in milliseconds
+/// * `blink_delay_ms` - Controller blink delay in milliseconds
+#[embassy_executor::task]
+async fn led_task(mut led: Output<'static>, tick_ms: u64, blink_delay_ms: u64) {
+    let mut controller = LedController::with_delay_ms(blink_delay_ms);
+    let mut pattern = LedPattern::Solid(LedState::Off);
+    let mut started = Instant::now();
+    loop {
+        if let Some(next) = LED_PATTERN.try_take() {
+            pattern = next;
+            started = Instant::now();
+        }
+        let on = led_state_to_level(controller.play(&pattern, started.elapsed().as_millis()));
+        led.set_level(Level::from(BOARD.led_polarity.level(on)));
+        if pattern.period_ms() == 0 {
+            LIVENESS.park(LED_TASK);
+            pattern = LED_PATTERN.wait().await;
+            started = Instant::now();
+            LIVENESS.unpark(LED_TASK);
+        } else {
+            LIVENESS.report(LED_TASK);
+            Timer::after_millis(tick_ms).await;
+        }
+    }
+}
+
+/// Core 1 input sampling task.
+///
+/// # Details
+/// Samples and debounces the button on its own executor so timing is
+/// unaffected by application load on core 0. Tracks wiring faults,
+/// bounce statistics and the BOOTSEL gesture like the single-core
+/// button task, and requests the matching LED pattern. Each debounced
+/// event is timestamped, queued for core 0 with the updated
+/// statistics and signalled. Events are counted and dropped if core 0
+/// falls a full queue behind. Reports liveness every sample.
+/// Idle sleep is not available: core 1 keeps polling so input timing
+/// stays fixed.
+///
+/// # Arguments
+/// * `button` - Button input pin, polarity from `BOARD`
+/// * `events` - Producer half of the input event queue
+/// * `settings` - Settings loaded at boot
+#[cfg(feature = "multicore")]
+#[embassy_executor::task]
+async fn input_task(
+    button: Input<'static>,
+    mut events: Producer<'static, InputReport, INPUT_QUEUE_LEN>,
+    settings: Settings,
+) {
+    let controller = ButtonController::with_strategy(DEBOUNCE_STRATEGY, settings.debounce_count);
+    let mut button_state = TrackedButton::from_parts(controller, settings.stats());
+    let mut bootsel = BootselDetector::new(BootselConfig::default(), Instant::now().as_millis());
+    let mut edge_timer = EdgeTimer::new();
+    let mut pressed_state = LedState::Off;
+    let mut current_pattern = None;
+    let mut dropped: u32 = 0;
+    loop {
+        let now = Instant::now();
+        let now_ms = now.as_millis();
+        let level = BOARD.button_polarity.to_active_low(button.is_high());
+        let event = button_state.update(level, now_ms);
+        if bootsel.update(event, now_ms) {
+            info!("rebooting into BOOTSEL");
+            reboot_to_bootsel();
+        }
+        if let Some(timed) = edge_timer.observe(button_state.controller(), event, now.as_ticks()) {
+            pressed_state = match timed.event {
+                ButtonEvent::Pressed => LedState::On,
+                ButtonEvent::Released => LedState::Off,
+            };
+            let report = InputReport {
+                timed,
+                stats: *button_state.stats(),
+            };
+            if events.push(report).is_err() {
+                dropped = dropped.saturating_add(1);
+                warn!("input queue full, {} events dropped", dropped);
+            }
+            INPUT_READY.signal(());
+        }
+        let pattern = button_led_pattern(&bootsel, &button_state, pressed_state, &settings, now_ms);
+        if current_pattern != Some(pattern) {
+            current_pattern = Some(pattern);
+            LED_PATTERN.signal(pattern);
+        }
+        LIVENESS.report(BUTTON_TASK);
+        Timer::after_millis(settings.debounce_delay_ms as u64).await;
+    }
+}
+
+/// Core 0 application task.
+///
+/// # Details
+/// Drains input events queued by core 1 and records their latency.
+/// The statistics reported with each release are saved to flash, at
+/// most once per interval; flash writes pause core 1 for their
+/// duration.
+///
+/// # Arguments
+/// * `events` - Consumer half of the input event queue
+/// * `store` - Flash settings store
+/// * `saved` - Settings loaded at boot
+#[cfg(feature = "multicore")]
+#[embassy_executor::task]
+async fn app_task(
+    mut events: Consumer<'static, InputReport, INPUT_QUEUE_LEN>,
+    mut store: SettingsStore<BoardFlash<'static>>,
+    mut saved: Settings,
+) {
+    let mut latency = LatencyHistogram::<LATENCY_BUCKETS>::new(
+        Duration::from_micros(LATENCY_BUCKET_US).as_ticks(),
+    );
+    let mut last_save_ms = 0;
+    loop {
+        INPUT_READY.wait().await;
+        while let Some(InputReport { timed, stats }) = events.pop() {
+            latency.record_event(&timed);
+            if timed.event == ButtonEvent::Released {
+                let now_ms = Instant::from_ticks(timed.confirmed_ticks).as_millis();
+                debug!("stats: {}", stats);
+                debug!("latency: {}", latency);
+                let current = saved.with_stats(&stats);
+                if current != saved
+                    && now_ms - last_save_ms >= SETTINGS_SAVE_INTERVAL_MS
+                    && store.save(&current).is_ok()
+                {
+                    saved = current;
+                    last_save_ms = now_ms;
+                }
+            }
+        }
+    }
+}
+
+/// PIO input bank task.
+///
+/// # Details
+/// Receives one sample word per debounce interval from the PIO state
+/// machine and debounces every bank pin at once with a vertical
+/// counter. Logs the debounced levels whenever any pin changes.
+///
+/// # Arguments
+/// * `_common` - PIO block holding the sampling program
+/// * `sm` - State machine sampling the input bank
+/// * `_pins` - Bank pins, kept configured while sampling
+#[cfg(feature = "pio-debounce")]
+#[embassy_executor::task]
+async fn pio_input_task(
+    _common: Common<'static, PIO0>,
+    mut sm: StateMachine<'static, PIO0, 0>,
+    _pins: [PioPin<'static, PIO0>; PIO_INPUT_COUNT as usize],
+) {
+    let mask = (1 << PIO_INPUT_COUNT) - 1;
+    let mut debouncer = VerticalDebouncer::new(DEBOUNCE_COUNT, mask);
+    loop {
+        let sample = sm.rx().wait_pull().await & mask;
+        let edges = debouncer.update(sample);
+        if edges != 0 {
+            info!("pio inputs {:#x}, changed {:#x}", debouncer.state(), edges);
+        }
+    }
+}
+
+/// Measures the charge time of the touch pad.
+///
+/// # Details
+/// Drives the pad low to discharge it, then releases it and counts
+/// polling iterations until the external pull-up charges it past the
+/// input threshold. Interrupts are masked while counting so the count
+/// is not stretched by other work. A finger adds capacitance and
+/// raises the count. Capped at `TOUCH_MAX_COUNT`.
+///
+/// # Arguments
+/// * `pad` - Touch pad pin
+///
+/// # Returns
+/// * `u32` - Charge-time count
+#[cfg(feature = "touch")]
+fn measure_charge_time(pad: &mut Flex<'static>) -> u32 {
+    pad.set_as_output();
+    pad.set_low();
+    embassy_time::block_for(Duration::from_micros(TOUCH_DISCHARGE_US));
+    cortex_m::interrupt::free(|_| {
+        pad.set_as_input();
+        let mut count = 0;
+        while pad.is_low() && count < TOUCH_MAX_COUNT {
+            count += 1;
+        }
+        count
+    })
+}
+
+/// Touch pad task.
+///
+/// # Details
+/// Measures the pad once per debounce interval and feeds the counts
+/// through `TouchSensor`, which calibrates its baseline on the first
+/// samples and debounces touches into button events. Events are
+/// logged.
+///
+/// # Arguments
+/// * `pad` - Touch pad pin, with an external pull-up
+/// * `debounce_delay_ms` - Sampling interval in milliseconds
+#[cfg(feature = "touch")]
+#[embassy_executor::task]
+async fn touch_task(mut pad: Flex<'static>, debounce_delay_ms: u64) {
+    pad.set_pull(Pull::None);
+    let mut sensor = TouchSensor::default();
+    loop {
+        let count = measure_charge_time(&mut pad);
+        if let Some(event) = sensor.update(count) {
+            info!(
+                "touch: {}, count {}, baseline {}",
+                event,
+                count,
+                sensor.baseline()
+            );
+        }
+        Timer::after_millis(debounce_delay_ms).await;
+    }
+}
 
 /// Main application entry point.
 ///
 /// # Details
-/// Initializes Embassy runtime and runs the main button polling loop.
-/// Uses ButtonController for state management with debouncing.
-/// Button on GPIO15 (active-low) controls LED on GPIO16.
+/// Initializes Embassy runtime, reports the reset cause, spawns the
+/// button and LED tasks and feeds the watchdog while both are alive.
+/// Button and LED pins come from the selected board profile. With the
+/// `multicore` feature the button is sampled and tracked on core 1
+/// and the application runs on core 0, without idle sleep.
 ///
 /// # Arguments
-/// * `_spawner` - Embassy task spawner (reserved for future async tasks).
+/// * `spawner` - Embassy task spawner.
 ///
 /// # Returns
 /// * `()` - Never returns (infinite loop).
 #[embassy_executor::main]
-async fn main(_spawner: Spawner) {
+async fn main(spawner: Spawner) {
     let p = embassy_rp::init(Default::default());
-    let button = Input::new(p.PIN_15, Pull::Up);
-    let mut led = Output::new(p.PIN_16, Level::Low);
-    let mut controller = ButtonController::new();
-    loop {
-        controller.update(button.is_high());
-        let state = if controller.is_pressed() {
-            LedState::On
-        } else {
-            LedState::Off
+    let mut watchdog = Watchdog::new(p.WATCHDOG);
+    let cause = match watchdog.reset_reason() {
+        Some(ResetReason::TimedOut) => ResetCause::WatchdogTimeout,
+        Some(ResetReason::Forced) => ResetCause::WatchdogForced,
+        None => ResetCause::PowerOn,
+    };
+    #[cfg(feature = "panic-led")]
+    report_previous_panic();
+    let (button_pin, led_pin) = board_pins!(p);
+    let mut button = Input::new(button_pin, gpio_pull(BUTTON_INPUT.pull));
+    apply_input_config(&mut button, &BUTTON_INPUT);
+    let led = Output::new(led_pin, Level::from(BOARD.led_polarity.level(false)));
+    let mut store = SettingsStore::new(
+        BoardFlash(Flash::new_blocking(p.FLASH)),
+        SETTINGS_FLASH_OFFSET,
+        SETTINGS_SECTOR_COUNT,
+    );
+    let saved = store.load();
+    info!(
+        "reset {}, board {}, button GPIO{}, led GPIO{}, settings {}",
+        cause, BOARD.name, BUTTON_PIN, LED_PIN, saved
+    );
+    spawner.spawn(led_task(led, saved.led_tick_ms as u64, saved.blink_delay_ms as u64).unwrap());
+    #[cfg(feature = "pio-debounce")]
+    {
+        let Pio {
+            mut common,
+            mut sm0,
+            ..
+        } = Pio::new(p.PIO0, Irqs);
+        // Y holds the delay loop count; each pass samples the bank and
+        // spins `PIO_SAMPLE_LOOPS + 3` cycles in total.
+        let program = pio::pio_asm!(
+            "pull block",
+            "mov y, osr",
+            ".wrap_target",
+            "in pins, 32",
+            "mov x, y",
+            "delay:",
+            "jmp x-- delay",
+            ".wrap",
+        );
+        let mut pins = [
+            common.make_pio_pin(board_pin!(p.PIN_2, PIO_INPUT_BASE_PIN)),
+            common.make_pio_pin(board_pin!(p.PIN_3, PIO_INPUT_BASE_PIN + 1)),
+            common.make_pio_pin(board_pin!(p.PIN_4, PIO_INPUT_BASE_PIN + 2)),
+            common.make_pio_pin(board_pin!(p.PIN_5, PIO_INPUT_BASE_PIN + 3)),
+        ];
+        for pin in pins.iter_mut() {
+            pin.set_pull(gpio_pull(PIO_INPUT.pull));
+            pin.set_schmitt(PIO_INPUT.schmitt);
+        }
+        let mut cfg = PioConfig::default();
+        cfg.use_program(&common.load_program(&program.program), &[]);
+        cfg.set_in_pins(&pins.each_ref());
+        cfg.shift_in = ShiftConfig {
+            auto_fill: true,
+            threshold: 32,
+            direction: ShiftDirection::Left,
         };
-        if led_state_to_level(state) {
-            led.set_high();
+        let divider = match pio_clock_divider_bits(clk_sys_freq()) {
+            Some(bits) => bits,
+            None => panic!("clk_sys is out of the PIO clock divider range"),
+        };
+        cfg.clock_divider = U24F8::from_bits(divider);
+        sm0.set_config(&cfg);
+        let loaded = sm0.tx().try_push(PIO_SAMPLE_LOOPS);
+        debug_assert!(loaded, "PIO TX FIFO is empty before enable");
+        sm0.set_enable(true);
+        spawner.spawn(pio_input_task(common, sm0, pins).unwrap());
+    }
+    #[cfg(feature = "touch")]
+    spawner.spawn(
+        touch_task(
+            Flex::new(board_pin!(p.PIN_22, TOUCH_PIN)),
+            saved.debounce_delay_ms as u64,
+        )
+        .unwrap(),
+    );
+    if cause.is_watchdog() {
+        warn!("watchdog reset detected");
+        LED_PATTERN.signal(cause.led_pattern());
+        Timer::after_millis(RESET_INDICATION_MS).await;
+    }
+    #[cfg(not(feature = "multicore"))]
+    spawner.spawn(button_task(button, store, saved).unwrap());
+    #[cfg(feature = "multicore")]
+    {
+        let (producer, consumer) = INPUT_EVENTS.split().unwrap();
+        // SAFETY: the core 1 stack is handed out exactly once, here.
+        let stack = unsafe { &mut *core::ptr::addr_of_mut!(CORE1_STACK) };
+        spawn_core1(p.CORE1, stack, move || {
+            let executor = CORE1_EXECUTOR.init(Executor::new());
+            executor.run(|spawner| {
+                spawner.spawn(input_task(button, producer, saved).unwrap());
+            })
+        });
+        spawner.spawn(app_task(consumer, store, saved).unwrap());
+    }
+    watchdog.start(Duration::from_millis(WATCHDOG_TIMEOUT_MS));
+    loop {
+        Timer::after_millis(WATCHDOG_FEED_MS).await;
+        if LIVENESS.check_and_clear() {
+            watchdog.feed();
         } else {
-            led.set_low();
+            error!("task stalled, withholding watchdog feed");
         }
-        Timer::after_millis(DEBOUNCE_DELAY_MS).await;
     }
 }
@@ -0,0 +1,479 @@
+/*
+ * @file morse.rs
+ * @brief Morse code tables and adaptive button decoder
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: morse.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Morse Code Input.
+//!
+//! BRIEF:
+//! International Morse code tables shared by input and output, and a
+//! decoder that turns debounced press and release edges into text.
+//! Dit and dah classification adapts to the sender's speed.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::ButtonEvent;
+use crate::config::MORSE_WPM;
+
+/// International Morse code for letters, digits and punctuation.
+#[allow(dead_code)]
+pub const MORSE_TABLE: [(char, &str); 54] = [
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
+
+/// Longest code in `MORSE_TABLE`, in elements.
+#[allow(dead_code)]
+pub const MAX_ELEMENTS: usize = 7;
+
+/// Shortest dit the decoder adapts to (60 WPM).
+const MIN_DIT_MS: u64 = 20;
+
+/// Longest dit the decoder adapts to (5 WPM).
+const MAX_DIT_MS: u64 = 240;
+
+/// Returns the Morse code of a character.
+///
+/// # Arguments
+/// * `c` - Character, letters in either case
+///
+/// # Returns
+/// * `Option<&'static str>` - Code of dots and dashes, None if unsupported
+#[allow(dead_code)]
+pub fn encode(c: char) -> Option<&'static str> {
+    let c = c.to_ascii_uppercase();
+    MORSE_TABLE
+        .iter()
+        .find(|&&(symbol, _)| symbol == c)
+        .map(|&(_, code)| code)
+}
+
+/// Returns the character of a Morse code.
+///
+/// # Arguments
+/// * `code` - Code of dots and dashes
+///
+/// # Returns
+/// * `Option<char>` - Character, None if unknown
+#[allow(dead_code)]
+pub fn decode(code: &str) -> Option<char> {
+    MORSE_TABLE
+        .iter()
+        .find(|&&(_, candidate)| candidate == code)
+        .map(|&(symbol, _)| symbol)
+}
+
+/// Returns the dit length for a speed.
+///
+/// # Details
+/// Uses the PARIS standard: one dit lasts 1200 / WPM milliseconds.
+///
+/// # Arguments
+/// * `wpm` - Words per minute, zero treated as one
+///
+/// # Returns
+/// * `u64` - Dit length in milliseconds
+#[allow(dead_code)]
+pub fn dit_ms(wpm: u32) -> u64 {
+    1200 / wpm.max(1) as u64
+}
+
+/// Decoded Morse output.
+///
+/// # Variants
+/// * `Char` - A decoded character
+/// * `Space` - A word gap
+/// * `Unknown` - A letter that matches no code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum MorseOutput {
+    Char(char),
+    Space,
+    Unknown,
+}
+
+/// Adaptive Morse key decoder.
+///
+/// # Details
+/// Presses shorter than two dits are dits, longer ones are dahs. Each
+/// element refines the dit estimate with a 1/4 weight, so the decoder
+/// follows a sender who speeds up or slows down. Silence of two dits
+/// ends a letter and five dits ends a word; these sit midway between
+/// the nominal 1, 3 and 7 unit gaps.
+///
+/// # Fields
+/// * `dit_ms` - Current dit length estimate
+/// * `elements` - Elements of the letter in progress
+/// * `len` - Number of elements stored
+/// * `overflow` - Letter has more elements than any code
+/// * `press_start_ms` - Start of the press in progress
+/// * `last_release_ms` - End of the most recent press
+/// * `space_pending` - A word gap may follow the last letter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct MorseDecoder {
+    dit_ms: u64,
+    elements: [u8; MAX_ELEMENTS],
+    len: usize,
+    overflow: bool,
+    press_start_ms: Option<u64>,
+    last_release_ms: Option<u64>,
+    space_pending: bool,
+}
+
+impl Default for MorseDecoder {
+    /// Returns default MorseDecoder instance.
+    ///
+    /// # Details
+    /// Delegates to new() for initialization.
+    ///
+    /// # Returns
+    /// * `Self` - New MorseDecoder with default values
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MorseDecoder {
+    /// Creates decoder starting at `MORSE_WPM`.
+    ///
+    /// # Returns
+    /// * `Self` - New MorseDecoder instance
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self::with_wpm(MORSE_WPM)
+    }
+
+    /// Creates decoder starting at a given speed.
+    ///
+    /// # Arguments
+    /// * `wpm` - Initial words per minute
+    ///
+    /// # Returns
+    /// * `Self` - New MorseDecoder instance
+    #[allow(dead_code)]
+    pub fn with_wpm(wpm: u32) -> Self {
+        Self {
+            dit_ms: dit_ms(wpm).clamp(MIN_DIT_MS, MAX_DIT_MS),
+            elements: [0; MAX_ELEMENTS],
+            len: 0,
+            overflow: false,
+            press_start_ms: None,
+            last_release_ms: None,
+            space_pending: false,
+        }
+    }
+
+    /// Advances the decoder.
+    ///
+    /// # Details
+    /// Call on every sample, with the debounced event if there is one,
+    /// so letter and word gaps are detected while the key is idle.
+    ///
+    /// # Arguments
+    /// * `event` - Debounced button event from this sample, if any
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<MorseOutput>` - Decoded output, if any
+    #[allow(dead_code)]
+    pub fn update(&mut self, event: Option<ButtonEvent>, now_ms: u64) -> Option<MorseOutput> {
+        match event {
+            Some(ButtonEvent::Pressed) => {
+                let output = self.poll(now_ms);
+                self.press_start_ms = Some(now_ms);
+                self.space_pending = false;
+                output
+            }
+            Some(ButtonEvent::Released) => {
+                if let Some(start) = self.press_start_ms.take() {
+                    self.push_element(now_ms.saturating_sub(start));
+                }
+                self.last_release_ms = Some(now_ms);
+                None
+            }
+            None => self.poll(now_ms),
+        }
+    }
+
+    /// Returns the current dit length estimate.
+    ///
+    /// # Returns
+    /// * `u64` - Dit length in milliseconds
+    #[allow(dead_code)]
+    pub fn dit_ms(&self) -> u64 {
+        self.dit_ms
+    }
+
+    /// Returns the current speed estimate.
+    ///
+    /// # Returns
+    /// * `u32` - Words per minute
+    #[allow(dead_code)]
+    pub fn wpm(&self) -> u32 {
+        (1200 / self.dit_ms) as u32
+    }
+
+    /// Classifies a press and adapts the dit estimate.
+    ///
+    /// # Arguments
+    /// * `duration_ms` - Press duration
+    fn push_element(&mut self, duration_ms: u64) {
+        let is_dah = duration_ms >= 2 * self.dit_ms;
+        let sample = if is_dah { duration_ms / 3 } else { duration_ms };
+        self.dit_ms = ((3 * self.dit_ms + sample) / 4).clamp(MIN_DIT_MS, MAX_DIT_MS);
+        if self.len == MAX_ELEMENTS {
+            self.overflow = true;
+            return;
+        }
+        self.elements[self.len] = if is_dah { b'-' } else { b'.' };
+        self.len += 1;
+    }
+
+    /// Checks for letter and word gaps while the key is up.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<MorseOutput>` - Completed letter or word gap
+    fn poll(&mut self, now_ms: u64) -> Option<MorseOutput> {
+        if self.press_start_ms.is_some() {
+            return None;
+        }
+        let gap = now_ms.saturating_sub(self.last_release_ms?);
+        if (self.len > 0 || self.overflow) && gap >= 2 * self.dit_ms {
+            self.space_pending = true;
+            return Some(self.take_letter());
+        }
+        if self.space_pending && gap >= 5 * self.dit_ms {
+            self.space_pending = false;
+            return Some(MorseOutput::Space);
+        }
+        None
+    }
+
+    /// Decodes and clears the letter in progress.
+    ///
+    /// # Returns
+    /// * `MorseOutput` - Decoded character or Unknown
+    fn take_letter(&mut self) -> MorseOutput {
+        let code = core::str::from_utf8(&self.elements[..self.len]).unwrap_or("");
+        let output = match decode(code) {
+            Some(c) if !self.overflow => MorseOutput::Char(c),
+            _ => MorseOutput::Unknown,
+        };
+        debug!("morse: {} -> {}", code, output);
+        self.len = 0;
+        self.overflow = false;
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys `codes` ("/" separates words) with `unit_ms` timing and
+    /// returns the decoded text, with `#` for unknown letters.
+    fn key(decoder: &mut MorseDecoder, codes: &str, unit_ms: u64) -> String {
+        let mut timeline = Vec::new();
+        let mut t = 0;
+        for word in codes.split('/') {
+            for letter in word.split(' ').filter(|l| !l.is_empty()) {
+                for element in letter.chars() {
+                    let length = if element == '-' { 3 } else { 1 };
+                    timeline.push((t, ButtonEvent::Pressed));
+                    t += length * unit_ms;
+                    timeline.push((t, ButtonEvent::Released));
+                    t += unit_ms;
+                }
+                t += 2 * unit_ms;
+            }
+            t += 4 * unit_ms;
+        }
+        let mut text = String::new();
+        let mut events = timeline.into_iter().peekable();
+        for now in 0..t + 10 * unit_ms {
+            let event = events.next_if(|&(at, _)| at == now).map(|(_, e)| e);
+            match decoder.update(event, now) {
+                Some(MorseOutput::Char(c)) => text.push(c),
+                Some(MorseOutput::Space) => text.push(' '),
+                Some(MorseOutput::Unknown) => text.push('#'),
+                None => {}
+            }
+        }
+        text
+    }
+
+    // ==================== Table Tests ====================
+
+    #[test]
+    fn test_encode_case_insensitive() {
+        assert_eq!(encode('s'), Some("..."));
+        assert_eq!(encode('S'), Some("..."));
+        assert_eq!(encode('~'), None);
+    }
+
+    #[test]
+    fn test_table_round_trips_and_unique() {
+        for (i, &(symbol, code)) in MORSE_TABLE.iter().enumerate() {
+            assert_eq!(decode(code), Some(symbol));
+            assert!(code.len() <= MAX_ELEMENTS);
+            assert!(MORSE_TABLE[i + 1..].iter().all(|&(_, other)| other != code));
+        }
+    }
+
+    #[test]
+    fn test_dit_length_from_wpm() {
+        assert_eq!(dit_ms(12), 100);
+        assert_eq!(dit_ms(20), 60);
+        assert_eq!(dit_ms(0), 1200);
+    }
+
+    // ==================== Decoder Tests ====================
+
+    #[test]
+    fn test_decodes_sos_at_nominal_speed() {
+        let mut decoder = MorseDecoder::with_wpm(12);
+        assert_eq!(key(&mut decoder, "... --- ...", 100), "SOS ");
+    }
+
+    #[test]
+    fn test_word_gaps_become_spaces() {
+        let mut decoder = MorseDecoder::with_wpm(12);
+        let text = key(&mut decoder, ".... ../-- .", 100);
+        assert_eq!(text, "HI ME ");
+    }
+
+    #[test]
+    fn test_adapts_to_faster_sender() {
+        let mut decoder = MorseDecoder::with_wpm(12);
+        let text = key(&mut decoder, "- . ... - / - . ... - / - . ... -", 40);
+        assert!(text.ends_with("TEST "), "{text}");
+        assert!(decoder.wpm() >= 25);
+    }
+
+    #[test]
+    fn test_adapts_to_slower_sender() {
+        let mut decoder = MorseDecoder::with_wpm(20);
+        let text = key(&mut decoder, "-- --- .-. ... . / -- --- .-. ... .", 150);
+        assert!(text.ends_with("MORSE "), "{text}");
+        assert!(decoder.wpm() <= 10);
+    }
+
+    #[test]
+    fn test_unknown_code_reported() {
+        let mut decoder = MorseDecoder::with_wpm(12);
+        assert_eq!(key(&mut decoder, "..--", 100), "# ");
+    }
+
+    #[test]
+    fn test_overlong_letter_reported_unknown() {
+        let mut decoder = MorseDecoder::with_wpm(12);
+        assert_eq!(key(&mut decoder, ".........", 100), "# ");
+    }
+
+    #[test]
+    fn test_no_output_while_idle() {
+        let mut decoder = MorseDecoder::new();
+        for now in 0..5000 {
+            assert_eq!(decoder.update(None, now), None);
+        }
+    }
+
+    #[test]
+    fn test_no_gap_output_while_held() {
+        let mut decoder = MorseDecoder::with_wpm(12);
+        decoder.update(Some(ButtonEvent::Pressed), 0);
+        decoder.update(Some(ButtonEvent::Released), 100);
+        decoder.update(Some(ButtonEvent::Pressed), 200);
+        for now in 200..2000 {
+            assert_eq!(decoder.update(None, now), None);
+        }
+    }
+}
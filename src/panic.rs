@@ -0,0 +1,372 @@
+/*
+ * @file panic.rs
+ * @brief Panic record kept in no-init RAM across resets
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: panic.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Panic Record and Indication.
+//!
+//! BRIEF:
+//! Captures the panic location and message into a fixed-size record
+//! that lives in no-init RAM, so it survives the watchdog reset that
+//! follows and can be reported at the next boot.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use core::fmt::{self, Write};
+
+use crate::config::{FAULT_BLINK_MS, FAULT_PAUSE_MS};
+use crate::led::LedPattern;
+use crate::settings::crc32;
+
+/// Magic number marking a captured panic record ("PANC").
+#[allow(dead_code)]
+pub const PANIC_MAGIC: u32 = 0x434E_4150;
+
+/// Maximum stored length of the panic file path in bytes.
+#[allow(dead_code)]
+pub const PANIC_FILE_LEN: usize = 48;
+
+/// Maximum stored length of the panic message in bytes.
+#[allow(dead_code)]
+pub const PANIC_MESSAGE_LEN: usize = 96;
+
+/// LED pattern shown by the panic handler.
+///
+/// # Details
+/// Five short pulses, distinct from the fault and reset patterns.
+#[allow(dead_code)]
+pub const PANIC_PATTERN: LedPattern = LedPattern::Burst {
+    count: 5,
+    on_ms: FAULT_BLINK_MS,
+    off_ms: FAULT_BLINK_MS,
+    pause_ms: FAULT_PAUSE_MS,
+};
+
+/// Truncating writer over a fixed byte buffer.
+///
+/// # Details
+/// Drops whatever does not fit, cutting only at character boundaries
+/// so the stored bytes remain valid UTF-8.
+///
+/// # Fields
+/// * `buf` - Destination buffer
+/// * `len` - Bytes written so far
+struct TruncatingWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for TruncatingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut take = s.len().min(self.buf.len() - self.len);
+        while !s.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.buf[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        Ok(())
+    }
+}
+
+/// Panic location and message record.
+///
+/// # Details
+/// Consists only of integers and byte arrays, so any bit pattern left
+/// in uninitialized RAM is a valid value; `is_valid` checks the magic
+/// number and checksum before the contents are trusted.
+///
+/// # Fields
+/// * `magic` - `PANIC_MAGIC` when a panic was captured
+/// * `line` - Source line of the panic
+/// * `column` - Source column of the panic
+/// * `file_len` - Bytes used in `file`
+/// * `message_len` - Bytes used in `message`
+/// * `file` - Source file path, truncated
+/// * `message` - Formatted panic message, truncated
+/// * `crc` - CRC-32 over the fields above
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+#[allow(dead_code)]
+pub struct PanicRecord {
+    magic: u32,
+    line: u32,
+    column: u32,
+    file_len: u32,
+    message_len: u32,
+    file: [u8; PANIC_FILE_LEN],
+    message: [u8; PANIC_MESSAGE_LEN],
+    crc: u32,
+}
+
+impl PanicRecord {
+    /// Creates an empty, invalid record.
+    ///
+    /// # Returns
+    /// * `Self` - New PanicRecord instance
+    #[allow(dead_code)]
+    pub const fn new() -> Self {
+        Self {
+            magic: 0,
+            line: 0,
+            column: 0,
+            file_len: 0,
+            message_len: 0,
+            file: [0; PANIC_FILE_LEN],
+            message: [0; PANIC_MESSAGE_LEN],
+            crc: 0,
+        }
+    }
+
+    /// Stores a panic location and message.
+    ///
+    /// # Details
+    /// Keeps the end of long file paths, where the file name is, and
+    /// the start of long messages.
+    ///
+    /// # Arguments
+    /// * `file` - Source file path
+    /// * `line` - Source line
+    /// * `column` - Source column
+    /// * `message` - Panic message
+    #[allow(dead_code)]
+    pub fn capture(&mut self, file: &str, line: u32, column: u32, message: impl fmt::Display) {
+        let mut start = file.len().saturating_sub(PANIC_FILE_LEN);
+        while !file.is_char_boundary(start) {
+            start += 1;
+        }
+        let file = &file[start..];
+        self.file[..file.len()].copy_from_slice(file.as_bytes());
+        self.file_len = file.len() as u32;
+        let mut writer = TruncatingWriter {
+            buf: &mut self.message,
+            len: 0,
+        };
+        let _ = write!(writer, "{}", message);
+        self.message_len = writer.len as u32;
+        self.line = line;
+        self.column = column;
+        self.magic = PANIC_MAGIC;
+        self.crc = self.checksum();
+    }
+
+    /// Returns true if the record holds a captured panic.
+    ///
+    /// # Returns
+    /// * `bool` - true if magic, lengths and checksum are consistent
+    #[allow(dead_code)]
+    pub fn is_valid(&self) -> bool {
+        self.magic == PANIC_MAGIC
+            && self.file_len as usize <= PANIC_FILE_LEN
+            && self.message_len as usize <= PANIC_MESSAGE_LEN
+            && self.crc == self.checksum()
+    }
+
+    /// Returns a copy of a valid record and clears the original.
+    ///
+    /// # Details
+    /// Always leaves the record empty, so garbage from a cold boot is
+    /// discarded and each panic is reported once.
+    ///
+    /// # Returns
+    /// * `Option<PanicRecord>` - Captured panic, if any
+    #[allow(dead_code)]
+    pub fn take(&mut self) -> Option<PanicRecord> {
+        let record = *self;
+        *self = Self::new();
+        record.is_valid().then_some(record)
+    }
+
+    /// Returns the stored source file path.
+    ///
+    /// # Returns
+    /// * `&str` - File path, empty if not valid UTF-8
+    #[allow(dead_code)]
+    pub fn file(&self) -> &str {
+        let len = (self.file_len as usize).min(PANIC_FILE_LEN);
+        core::str::from_utf8(&self.file[..len]).unwrap_or("")
+    }
+
+    /// Returns the stored source line.
+    ///
+    /// # Returns
+    /// * `u32` - Line number
+    #[allow(dead_code)]
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the stored source column.
+    ///
+    /// # Returns
+    /// * `u32` - Column number
+    #[allow(dead_code)]
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// Returns the stored panic message.
+    ///
+    /// # Returns
+    /// * `&str` - Message, empty if not valid UTF-8
+    #[allow(dead_code)]
+    pub fn message(&self) -> &str {
+        let len = (self.message_len as usize).min(PANIC_MESSAGE_LEN);
+        core::str::from_utf8(&self.message[..len]).unwrap_or("")
+    }
+
+    /// Computes the record checksum.
+    ///
+    /// # Returns
+    /// * `u32` - CRC-32 of every field except `crc`
+    fn checksum(&self) -> u32 {
+        let mut bytes = [0u8; 20 + PANIC_FILE_LEN + PANIC_MESSAGE_LEN];
+        let header = [
+            self.magic,
+            self.line,
+            self.column,
+            self.file_len,
+            self.message_len,
+        ];
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(header) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes[20..20 + PANIC_FILE_LEN].copy_from_slice(&self.file);
+        bytes[20 + PANIC_FILE_LEN..].copy_from_slice(&self.message);
+        crc32(&bytes)
+    }
+}
+
+impl Default for PanicRecord {
+    /// Returns an empty record.
+    ///
+    /// # Returns
+    /// * `Self` - Empty PanicRecord
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::led::LedState;
+
+    fn captured() -> PanicRecord {
+        let mut record = PanicRecord::new();
+        record.capture(
+            "src/main.rs",
+            42,
+            7,
+            format_args!("index {} out of range", 9),
+        );
+        record
+    }
+
+    // ==================== Capture Tests ====================
+
+    #[test]
+    fn test_new_record_invalid() {
+        assert!(!PanicRecord::new().is_valid());
+    }
+
+    #[test]
+    fn test_capture_stores_location_and_message() {
+        let record = captured();
+        assert!(record.is_valid());
+        assert_eq!(record.file(), "src/main.rs");
+        assert_eq!(record.line(), 42);
+        assert_eq!(record.column(), 7);
+        assert_eq!(record.message(), "index 9 out of range");
+    }
+
+    #[test]
+    fn test_long_message_truncated() {
+        let mut record = PanicRecord::new();
+        let long = "x".repeat(PANIC_MESSAGE_LEN + 20);
+        record.capture("a.rs", 1, 1, long.as_str());
+        assert_eq!(record.message().len(), PANIC_MESSAGE_LEN);
+        assert!(record.is_valid());
+    }
+
+    #[test]
+    fn test_truncation_keeps_utf8_valid() {
+        let mut record = PanicRecord::new();
+        let long = "é".repeat(PANIC_MESSAGE_LEN);
+        record.capture("a.rs", 1, 1, long.as_str());
+        assert_eq!(record.message().len(), PANIC_MESSAGE_LEN);
+        assert!(record.message().chars().all(|c| c == 'é'));
+    }
+
+    #[test]
+    fn test_long_file_keeps_tail() {
+        let mut record = PanicRecord::new();
+        let path = format!("{}/button.rs", "dir".repeat(30));
+        record.capture(&path, 1, 1, "boom");
+        assert_eq!(record.file().len(), PANIC_FILE_LEN);
+        assert!(record.file().ends_with("/button.rs"));
+    }
+
+    // ==================== Take Tests ====================
+
+    #[test]
+    fn test_take_returns_once() {
+        let mut record = captured();
+        let taken = record.take().unwrap();
+        assert_eq!(taken.line(), 42);
+        assert_eq!(record.take(), None);
+    }
+
+    #[test]
+    fn test_corrupted_record_rejected() {
+        let mut record = captured();
+        record.message[0] ^= 0xFF;
+        assert!(!record.is_valid());
+        assert_eq!(record.take(), None);
+        assert!(!record.is_valid());
+    }
+
+    #[test]
+    fn test_bad_lengths_rejected() {
+        let mut record = captured();
+        record.message_len = PANIC_MESSAGE_LEN as u32 + 1;
+        record.crc = record.checksum();
+        assert!(!record.is_valid());
+    }
+
+    // ==================== Pattern Tests ====================
+
+    #[test]
+    fn test_panic_pattern_blinks() {
+        assert_eq!(PANIC_PATTERN.state_at(0), LedState::On);
+        assert!(PANIC_PATTERN.period_ms() > 0);
+    }
+}
@@ -0,0 +1,227 @@
+/*
+ * @file power.rs
+ * @brief Idle timeout policy for waiting on the button
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: power.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Idle Timeout Policy.
+//!
+//! BRIEF:
+//! Decides when the firmware may stop polling and wait for the button
+//! as wake source. Configures which events count as activity.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::ButtonEvent;
+use crate::config::IDLE_TIMEOUT_MS;
+
+/// Idle timeout policy.
+///
+/// # Fields
+/// * `timeout_ms` - Inactivity required before sleeping
+/// * `press_resets` - Presses restart the idle timer
+/// * `release_resets` - Releases restart the idle timer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct IdlePolicy {
+    pub timeout_ms: u64,
+    pub press_resets: bool,
+    pub release_resets: bool,
+}
+
+impl Default for IdlePolicy {
+    /// Returns policy with `IDLE_TIMEOUT_MS` and all events as activity.
+    ///
+    /// # Returns
+    /// * `Self` - Default idle policy
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            timeout_ms: IDLE_TIMEOUT_MS,
+            press_resets: true,
+            release_resets: true,
+        }
+    }
+}
+
+/// Idle timer driven by button activity.
+///
+/// # Details
+/// Never requests sleep while the button is held, since the wake
+/// source is the press edge.
+///
+/// # Fields
+/// * `policy` - Idle timeout policy
+/// * `last_activity_ms` - Timestamp of the most recent activity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct IdleTimer {
+    policy: IdlePolicy,
+    last_activity_ms: u64,
+}
+
+impl IdleTimer {
+    /// Creates idle timer starting at `now_ms`.
+    ///
+    /// # Arguments
+    /// * `policy` - Idle timeout policy
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Self` - New IdleTimer instance
+    #[allow(dead_code)]
+    pub fn new(policy: IdlePolicy, now_ms: u64) -> Self {
+        Self {
+            policy,
+            last_activity_ms: now_ms,
+        }
+    }
+
+    /// Records a button event.
+    ///
+    /// # Details
+    /// Restarts the timer if the policy counts the event as activity.
+    ///
+    /// # Arguments
+    /// * `event` - Debounced button event
+    /// * `now_ms` - Current timestamp in milliseconds
+    #[allow(dead_code)]
+    pub fn record(&mut self, event: ButtonEvent, now_ms: u64) {
+        let resets = match event {
+            ButtonEvent::Pressed => self.policy.press_resets,
+            ButtonEvent::Released => self.policy.release_resets,
+        };
+        if resets {
+            self.last_activity_ms = now_ms;
+        }
+    }
+
+    /// Restarts the timer after waking from sleep.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    #[allow(dead_code)]
+    pub fn wake(&mut self, now_ms: u64) {
+        self.last_activity_ms = now_ms;
+    }
+
+    /// Returns true if the device should enter sleep.
+    ///
+    /// # Arguments
+    /// * `pressed` - Current debounced button state
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `bool` - true if idle for at least the timeout and released
+    #[allow(dead_code)]
+    pub fn should_sleep(&self, pressed: bool, now_ms: u64) -> bool {
+        !pressed && now_ms.saturating_sub(self.last_activity_ms) >= self.policy.timeout_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(press_resets: bool, release_resets: bool) -> IdlePolicy {
+        IdlePolicy {
+            timeout_ms: 1000,
+            press_resets,
+            release_resets,
+        }
+    }
+
+    // ==================== Timeout Tests ====================
+
+    #[test]
+    fn test_default_policy_uses_config() {
+        assert_eq!(IdlePolicy::default().timeout_ms, IDLE_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn test_not_sleepy_before_timeout() {
+        let timer = IdleTimer::new(policy(true, true), 100);
+        assert!(!timer.should_sleep(false, 1099));
+    }
+
+    #[test]
+    fn test_sleepy_after_timeout() {
+        let timer = IdleTimer::new(policy(true, true), 100);
+        assert!(timer.should_sleep(false, 1100));
+    }
+
+    #[test]
+    fn test_never_sleep_while_pressed() {
+        let timer = IdleTimer::new(policy(true, true), 0);
+        assert!(!timer.should_sleep(true, 10_000));
+    }
+
+    // ==================== Activity Policy Tests ====================
+
+    #[test]
+    fn test_press_resets_timer() {
+        let mut timer = IdleTimer::new(policy(true, false), 0);
+        timer.record(ButtonEvent::Pressed, 900);
+        assert!(!timer.should_sleep(false, 1500));
+        assert!(timer.should_sleep(false, 1900));
+    }
+
+    #[test]
+    fn test_release_ignored_when_disabled() {
+        let mut timer = IdleTimer::new(policy(true, false), 0);
+        timer.record(ButtonEvent::Released, 900);
+        assert!(timer.should_sleep(false, 1000));
+    }
+
+    #[test]
+    fn test_release_resets_when_enabled() {
+        let mut timer = IdleTimer::new(policy(false, true), 0);
+        timer.record(ButtonEvent::Pressed, 500);
+        timer.record(ButtonEvent::Released, 900);
+        assert!(!timer.should_sleep(false, 1500));
+    }
+
+    #[test]
+    fn test_wake_restarts_timer() {
+        let mut timer = IdleTimer::new(policy(false, false), 0);
+        timer.wake(5000);
+        assert!(!timer.should_sleep(false, 5500));
+        assert!(timer.should_sleep(false, 6000));
+    }
+
+    #[test]
+    fn test_clock_going_backwards_not_sleepy() {
+        let timer = IdleTimer::new(policy(true, true), 5000);
+        assert!(!timer.should_sleep(false, 0));
+    }
+}
@@ -0,0 +1,546 @@
+/*
+ * @file queue.rs
+ * @brief Lock-free single-producer single-consumer event queue
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: queue.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Lock-Free SPSC Event Queue.
+//!
+//! BRIEF:
+//! Fixed-capacity ring buffer passing events from one producer to one
+//! consumer without locks. Works between tasks on one core, between an
+//! interrupt handler and a task in either direction, or from core 1 to
+//! core 0. A full queue can either reject new events or discard its
+//! oldest one.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Lock-free single-producer single-consumer queue.
+///
+/// # Details
+/// Head and tail are free-running counters reduced modulo `N`, so `N`
+/// must be a power of two. Only the producer advances `tail`; each side
+/// publishes with `Release` and observes the other with `Acquire`.
+/// The consumer reads and advances `head` while holding `claim`. The
+/// producer only touches `head` in `force_push`. Both sides take
+/// `claim` with a single swap and back off if the other side holds it:
+/// `force_push` hands the event back and `pop` or `peek` report no
+/// event. Neither side ever spins, so either may preempt the other
+/// without deadlock; the consumer simply retries once the producer's
+/// next wake-up arrives. Access goes through the `Producer` and
+/// `Consumer` handles from `split`, which hands them out once so there
+/// can never be a second producer or consumer.
+///
+/// # Fields
+/// * `slots` - Event storage
+/// * `head` - Count of events consumed or discarded
+/// * `tail` - Count of events produced
+/// * `claim` - Held while `head` and the oldest slot are in use
+/// * `split` - Set once the handles have been taken
+#[allow(dead_code)]
+pub struct SpscQueue<T: Copy, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    claim: AtomicBool,
+    split: AtomicBool,
+}
+
+// SAFETY: a slot is written only by the single producer before `tail`
+// is released past it, and read only by the single consumer after
+// acquiring that `tail`. The producer reuses the oldest slot only while
+// holding `claim`, which the consumer holds for every read, so no slot
+// is accessed from two contexts at once.
+unsafe impl<T: Copy + Send, const N: usize> Sync for SpscQueue<T, N> {}
+
+impl<T: Copy, const N: usize> Default for SpscQueue<T, N> {
+    /// Returns empty queue.
+    ///
+    /// # Returns
+    /// * `Self` - Empty SpscQueue
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, const N: usize> SpscQueue<T, N> {
+    /// Creates empty queue.
+    ///
+    /// # Returns
+    /// * `Self` - New SpscQueue instance
+    #[allow(dead_code)]
+    pub const fn new() -> Self {
+        const { assert!(N.is_power_of_two(), "queue capacity must be a power of two") };
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            claim: AtomicBool::new(false),
+            split: AtomicBool::new(false),
+        }
+    }
+
+    /// Runs consumer code while holding the claim on `head`.
+    ///
+    /// # Details
+    /// Does not wait for the claim. If `force_push` holds it, the
+    /// producer is mid-update, possibly in a context this one
+    /// preempted, so the consumer backs off and reports no event.
+    ///
+    /// # Arguments
+    /// * `f` - Code reading the oldest slot or advancing `head`
+    ///
+    /// # Returns
+    /// * `Option<R>` - Result of `f`, or None if the producer held the claim
+    fn claimed<R>(&self, f: impl FnOnce() -> Option<R>) -> Option<R> {
+        if self.claim.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let result = f();
+        self.claim.store(false, Ordering::Release);
+        result
+    }
+
+    /// Takes the producer and consumer handles.
+    ///
+    /// # Returns
+    /// * `Option<(Producer, Consumer)>` - Handles, or None if already taken
+    #[allow(dead_code)]
+    pub fn split(&self) -> Option<(Producer<'_, T, N>, Consumer<'_, T, N>)> {
+        if self.split.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        Some((Producer { queue: self }, Consumer { queue: self }))
+    }
+
+    /// Returns number of queued events.
+    ///
+    /// # Returns
+    /// * `usize` - Events waiting to be consumed
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+
+    /// Returns true if no events are queued.
+    ///
+    /// # Returns
+    /// * `bool` - true if empty
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns queue capacity.
+    ///
+    /// # Returns
+    /// * `usize` - Maximum number of queued events
+    #[allow(dead_code)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+/// Producing half of an `SpscQueue`.
+///
+/// # Fields
+/// * `queue` - Shared queue
+#[allow(dead_code)]
+pub struct Producer<'a, T: Copy, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+}
+
+impl<T: Copy, const N: usize> Producer<'_, T, N> {
+    /// Appends an event.
+    ///
+    /// # Arguments
+    /// * `item` - Event to queue
+    ///
+    /// # Returns
+    /// * `Result<(), T>` - Ok, or the event back if the queue is full
+    #[allow(dead_code)]
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.queue.head.load(Ordering::Acquire)) == N {
+            return Err(item);
+        }
+        // SAFETY: the slot is outside the consumer's readable range until
+        // `tail` is released below.
+        unsafe { (*self.queue.slots[tail % N].get()).write(item) };
+        self.queue
+            .tail
+            .store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Appends an event, discarding the oldest one if the queue is full.
+    ///
+    /// # Details
+    /// Never waits, so it may be called from an interrupt handler. If
+    /// the consumer is reading at that moment the queue is left
+    /// unchanged and the event is returned instead.
+    ///
+    /// # Arguments
+    /// * `item` - Event to queue
+    ///
+    /// # Returns
+    /// * `Result<Option<T>, T>` - Discarded oldest event if any, or the
+    ///   event back if the consumer held the queue
+    #[allow(dead_code)]
+    pub fn force_push(&mut self, item: T) -> Result<Option<T>, T> {
+        let item = match self.push(item) {
+            Ok(()) => return Ok(None),
+            Err(item) => item,
+        };
+        if self.queue.claim.swap(true, Ordering::Acquire) {
+            return Err(item);
+        }
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        let mut dropped = None;
+        if tail.wrapping_sub(head) == N {
+            // SAFETY: the slot holds a produced event and the consumer
+            // cannot read it while `claim` is held.
+            dropped = Some(unsafe { (*self.queue.slots[head % N].get()).assume_init() });
+            self.queue
+                .head
+                .store(head.wrapping_add(1), Ordering::Release);
+        }
+        // SAFETY: the slot is free, either consumed or discarded above,
+        // and stays outside the consumer's range until `tail` is released.
+        unsafe { (*self.queue.slots[tail % N].get()).write(item) };
+        self.queue
+            .tail
+            .store(tail.wrapping_add(1), Ordering::Release);
+        self.queue.claim.store(false, Ordering::Release);
+        Ok(dropped)
+    }
+
+    /// Returns true if the queue has no free slot.
+    ///
+    /// # Returns
+    /// * `bool` - true if full
+    #[allow(dead_code)]
+    pub fn is_full(&self) -> bool {
+        self.queue.len() == N
+    }
+}
+
+/// Consuming half of an `SpscQueue`.
+///
+/// # Fields
+/// * `queue` - Shared queue
+#[allow(dead_code)]
+pub struct Consumer<'a, T: Copy, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+}
+
+impl<T: Copy, const N: usize> Consumer<'_, T, N> {
+    /// Removes the oldest event.
+    ///
+    /// # Details
+    /// Never waits. Returns None while the producer is discarding an
+    /// event in `force_push`; the new event is visible on the next call.
+    ///
+    /// # Returns
+    /// * `Option<T>` - Oldest event, or None if empty or busy
+    #[allow(dead_code)]
+    pub fn pop(&mut self) -> Option<T> {
+        let queue = self.queue;
+        queue.claimed(|| {
+            let head = queue.head.load(Ordering::Acquire);
+            if head == queue.tail.load(Ordering::Acquire) {
+                return None;
+            }
+            // SAFETY: the acquire load of `tail` makes the producer's write
+            // to this slot visible, and the producer will not reuse it
+            // until `head` is released below and `claim` is dropped.
+            let item = unsafe { (*queue.slots[head % N].get()).assume_init() };
+            queue.head.store(head.wrapping_add(1), Ordering::Release);
+            Some(item)
+        })
+    }
+
+    /// Returns the oldest event without removing it.
+    ///
+    /// # Details
+    /// Never waits; backs off like `pop`.
+    ///
+    /// # Returns
+    /// * `Option<T>` - Oldest event, or None if empty or busy
+    #[allow(dead_code)]
+    pub fn peek(&self) -> Option<T> {
+        let queue = self.queue;
+        queue.claimed(|| {
+            let head = queue.head.load(Ordering::Acquire);
+            if head == queue.tail.load(Ordering::Acquire) {
+                return None;
+            }
+            // SAFETY: as in `pop`; the slot stays owned by the consumer.
+            Some(unsafe { (*queue.slots[head % N].get()).assume_init() })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::ButtonEvent;
+    use std::thread;
+
+    // ==================== Handle Tests ====================
+
+    #[test]
+    fn test_split_only_once() {
+        let queue = SpscQueue::<u32, 4>::new();
+        assert!(queue.split().is_some());
+        assert!(queue.split().is_none());
+    }
+
+    #[test]
+    fn test_new_queue_empty() {
+        let queue = SpscQueue::<u32, 8>::default();
+        let (_, mut rx) = queue.split().unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(rx.pop(), None);
+    }
+
+    // ==================== Ordering Tests ====================
+
+    #[test]
+    fn test_fifo_order() {
+        let queue = SpscQueue::<ButtonEvent, 4>::new();
+        let (mut tx, mut rx) = queue.split().unwrap();
+        tx.push(ButtonEvent::Pressed).unwrap();
+        tx.push(ButtonEvent::Released).unwrap();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(rx.peek(), Some(ButtonEvent::Pressed));
+        assert_eq!(rx.pop(), Some(ButtonEvent::Pressed));
+        assert_eq!(rx.pop(), Some(ButtonEvent::Released));
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_full_queue_returns_item() {
+        let queue = SpscQueue::<u32, 4>::new();
+        let (mut tx, mut rx) = queue.split().unwrap();
+        for i in 0..4 {
+            tx.push(i).unwrap();
+        }
+        assert!(tx.is_full());
+        assert_eq!(tx.push(99), Err(99));
+        assert_eq!(rx.pop(), Some(0));
+        assert!(tx.push(4).is_ok());
+    }
+
+    #[test]
+    fn test_wraparound_keeps_order() {
+        let queue = SpscQueue::<u32, 4>::new();
+        let (mut tx, mut rx) = queue.split().unwrap();
+        for i in 0..100 {
+            tx.push(i).unwrap();
+            tx.push(i + 1000).unwrap();
+            assert_eq!(rx.pop(), Some(i));
+            assert_eq!(rx.pop(), Some(i + 1000));
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_counters_wrap_at_usize_max() {
+        let queue = SpscQueue::<u32, 4>::new();
+        queue.head.store(usize::MAX - 1, Ordering::Relaxed);
+        queue.tail.store(usize::MAX - 1, Ordering::Relaxed);
+        let (mut tx, mut rx) = queue.split().unwrap();
+        for i in 0..4 {
+            tx.push(i).unwrap();
+        }
+        assert_eq!(tx.push(4), Err(4));
+        assert_eq!(queue.len(), 4);
+        for i in 0..4 {
+            assert_eq!(rx.pop(), Some(i));
+        }
+    }
+
+    // ==================== Overwrite Tests ====================
+
+    #[test]
+    fn test_force_push_discards_oldest() {
+        let queue = SpscQueue::<u32, 2>::new();
+        let (mut tx, mut rx) = queue.split().unwrap();
+        assert_eq!(tx.force_push(1), Ok(None));
+        assert_eq!(tx.force_push(2), Ok(None));
+        assert_eq!(tx.force_push(3), Ok(Some(1)));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(rx.pop(), Some(2));
+        assert_eq!(rx.pop(), Some(3));
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_force_push_backs_off_while_claimed() {
+        let queue = SpscQueue::<u32, 2>::new();
+        let (mut tx, _rx) = queue.split().unwrap();
+        tx.push(1).unwrap();
+        tx.push(2).unwrap();
+        queue.claim.store(true, Ordering::Relaxed);
+        assert_eq!(tx.force_push(3), Err(3));
+        queue.claim.store(false, Ordering::Relaxed);
+        assert_eq!(tx.force_push(3), Ok(Some(1)));
+    }
+
+    #[test]
+    fn test_pop_backs_off_while_producer_claims() {
+        let queue = SpscQueue::<u32, 2>::new();
+        let (mut tx, mut rx) = queue.split().unwrap();
+        tx.push(1).unwrap();
+        queue.claim.store(true, Ordering::Relaxed);
+        assert_eq!(rx.peek(), None);
+        assert_eq!(rx.pop(), None);
+        assert_eq!(queue.len(), 1);
+        queue.claim.store(false, Ordering::Relaxed);
+        assert_eq!(rx.peek(), Some(1));
+        assert_eq!(rx.pop(), Some(1));
+        assert!(!queue.claim.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_force_push_wraps_at_usize_max() {
+        let queue = SpscQueue::<u32, 2>::new();
+        queue.head.store(usize::MAX, Ordering::Relaxed);
+        queue.tail.store(usize::MAX, Ordering::Relaxed);
+        let (mut tx, mut rx) = queue.split().unwrap();
+        for i in 0..5 {
+            tx.force_push(i).unwrap();
+        }
+        assert_eq!(queue.len(), 2);
+        assert_eq!(rx.pop(), Some(3));
+        assert_eq!(rx.pop(), Some(4));
+        assert!(queue.is_empty());
+    }
+
+    // ==================== Concurrency Tests ====================
+
+    #[test]
+    fn test_threaded_transfer_preserves_sequence() {
+        static QUEUE: SpscQueue<u32, 8> = SpscQueue::new();
+        let (mut tx, mut rx) = QUEUE.split().unwrap();
+        const COUNT: u32 = 100_000;
+        let producer = thread::spawn(move || {
+            for i in 0..COUNT {
+                while tx.push(i).is_err() {
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        while expected < COUNT {
+            match rx.pop() {
+                Some(value) => {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_threaded_len_never_exceeds_capacity() {
+        static QUEUE: SpscQueue<u64, 4> = SpscQueue::new();
+        let (mut tx, mut rx) = QUEUE.split().unwrap();
+        let producer = thread::spawn(move || {
+            for i in 0..5_000u64 {
+                while tx.push(i).is_err() {
+                    thread::yield_now();
+                }
+                assert!(QUEUE.len() <= 4);
+            }
+        });
+        let mut sum = 0;
+        let mut received = 0;
+        while received < 5_000 {
+            match rx.pop() {
+                Some(value) => {
+                    sum += value;
+                    received += 1;
+                }
+                None => thread::yield_now(),
+            }
+            assert!(QUEUE.len() <= 4);
+        }
+        producer.join().unwrap();
+        assert_eq!(sum, (0..5_000u64).sum());
+    }
+
+    #[test]
+    fn test_threaded_force_push_accounts_for_every_event() {
+        static QUEUE: SpscQueue<u32, 2> = SpscQueue::new();
+        let (mut tx, mut rx) = QUEUE.split().unwrap();
+        const COUNT: u32 = 50_000;
+        let producer = thread::spawn(move || {
+            let mut lost = Vec::new();
+            for i in 0..COUNT {
+                match tx.force_push(i) {
+                    Ok(None) => {}
+                    Ok(Some(dropped)) => lost.push(dropped),
+                    Err(rejected) => lost.push(rejected),
+                }
+            }
+            lost
+        });
+        let mut received = Vec::new();
+        while !producer.is_finished() || !QUEUE.is_empty() {
+            if let Some(value) = rx.peek() {
+                assert_eq!(rx.pop(), Some(value));
+                assert!(received.last().is_none_or(|&last| value > last));
+                received.push(value);
+            }
+        }
+        let lost = producer.join().unwrap();
+        let mut seen = vec![false; COUNT as usize];
+        for value in received.iter().chain(&lost) {
+            assert!(!seen[*value as usize], "{value} delivered twice");
+            seen[*value as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert!(!received.is_empty());
+    }
+}
@@ -0,0 +1,469 @@
+/*
+ * @file ring.rs
+ * @brief ISR-safe button event ring buffer with overflow policies
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: ring.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Button Event Ring Buffer.
+//!
+//! BRIEF:
+//! Fixed-capacity, lock-free ring of `ButtonEvent`s shared between one
+//! producer, such as an interrupt handler, and one consumer, such as
+//! an async task. Adds overflow policies and loss counting on top of
+//! `SpscQueue`.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::button::ButtonEvent;
+use crate::queue::{Consumer, Producer, SpscQueue};
+
+/// Behaviour when an event arrives at a full ring.
+///
+/// # Variants
+/// * `DropNewest` - Discard the incoming event
+/// * `DropOldest` - Discard the oldest queued event to make room
+/// * `CoalesceRepeats` - Absorb an incoming repeat of the last pushed
+///   event; discard anything else
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum OverflowPolicy {
+    DropNewest,
+    DropOldest,
+    CoalesceRepeats,
+}
+
+/// Outcome of a push.
+///
+/// # Variants
+/// * `Queued` - Event was queued
+/// * `DroppedNewest` - Ring was full; the incoming event was discarded
+/// * `DroppedOldest` - Ring was full; the oldest event made room
+/// * `Coalesced` - Ring was full; the event repeated the last one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum PushResult {
+    Queued,
+    DroppedNewest,
+    DroppedOldest,
+    Coalesced,
+}
+
+/// Button event ring with an overflow policy.
+///
+/// # Details
+/// A thin layer over `SpscQueue` that applies `policy` when the queue
+/// is full and counts the events lost. Safe to share as a `static`
+/// between one producer and one consumer on the same or different
+/// cores; `split` hands out the two handles once. `N` must be a power
+/// of two.
+///
+/// # Fields
+/// * `queue` - Event storage
+/// * `overflows` - Events lost to overflow
+/// * `policy` - Overflow policy
+#[allow(dead_code)]
+pub struct EventRing<const N: usize> {
+    queue: SpscQueue<ButtonEvent, N>,
+    overflows: AtomicU32,
+    policy: OverflowPolicy,
+}
+
+impl<const N: usize> EventRing<N> {
+    /// Creates empty ring.
+    ///
+    /// # Arguments
+    /// * `policy` - Overflow policy
+    ///
+    /// # Returns
+    /// * `Self` - New EventRing instance
+    #[allow(dead_code)]
+    pub const fn new(policy: OverflowPolicy) -> Self {
+        Self {
+            queue: SpscQueue::new(),
+            overflows: AtomicU32::new(0),
+            policy,
+        }
+    }
+
+    /// Takes the producer and consumer handles.
+    ///
+    /// # Returns
+    /// * `Option<(EventProducer, Consumer)>` - Handles, or None if already taken
+    #[allow(dead_code)]
+    pub fn split(&self) -> Option<(EventProducer<'_, N>, Consumer<'_, ButtonEvent, N>)> {
+        let (producer, consumer) = self.queue.split()?;
+        let producer = EventProducer {
+            producer,
+            ring: self,
+            last: None,
+        };
+        Some((producer, consumer))
+    }
+
+    /// Returns number of queued events.
+    ///
+    /// # Returns
+    /// * `usize` - Events waiting to be consumed
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if no events are queued.
+    ///
+    /// # Returns
+    /// * `bool` - true if empty
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns number of events lost to overflow.
+    ///
+    /// # Details
+    /// Coalesced repeats are not counted, since no information is lost.
+    ///
+    /// # Returns
+    /// * `u32` - Overflow count
+    #[allow(dead_code)]
+    pub fn overflows(&self) -> u32 {
+        self.overflows.load(Ordering::Relaxed)
+    }
+
+    /// Returns the overflow policy.
+    ///
+    /// # Returns
+    /// * `OverflowPolicy` - Overflow policy
+    #[allow(dead_code)]
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+}
+
+/// Producing half of an `EventRing`.
+///
+/// # Fields
+/// * `producer` - Producer handle of the underlying queue
+/// * `ring` - Shared ring, for the policy and overflow count
+/// * `last` - Last pushed event
+#[allow(dead_code)]
+pub struct EventProducer<'a, const N: usize> {
+    producer: Producer<'a, ButtonEvent, N>,
+    ring: &'a EventRing<N>,
+    last: Option<ButtonEvent>,
+}
+
+impl<const N: usize> EventProducer<'_, N> {
+    /// Pushes an event.
+    ///
+    /// # Details
+    /// Never blocks, so it may be called from an interrupt handler.
+    /// Under `DropOldest`, an event that arrives while the consumer is
+    /// reading the oldest slot is discarded instead.
+    ///
+    /// # Arguments
+    /// * `event` - Event to queue
+    ///
+    /// # Returns
+    /// * `PushResult` - What happened to the event
+    #[allow(dead_code)]
+    pub fn push(&mut self, event: ButtonEvent) -> PushResult {
+        let last = self.last.replace(event);
+        let result = match self.ring.policy {
+            OverflowPolicy::DropOldest => match self.producer.force_push(event) {
+                Ok(None) => PushResult::Queued,
+                Ok(Some(_)) => PushResult::DroppedOldest,
+                Err(_) => PushResult::DroppedNewest,
+            },
+            policy => match self.producer.push(event) {
+                Ok(()) => PushResult::Queued,
+                Err(_) if policy == OverflowPolicy::CoalesceRepeats && last == Some(event) => {
+                    PushResult::Coalesced
+                }
+                Err(_) => PushResult::DroppedNewest,
+            },
+        };
+        if matches!(
+            result,
+            PushResult::DroppedNewest | PushResult::DroppedOldest
+        ) {
+            self.ring.overflows.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::thread;
+    use ButtonEvent::{Pressed, Released};
+
+    const POLICIES: [OverflowPolicy; 3] = [
+        OverflowPolicy::DropNewest,
+        OverflowPolicy::DropOldest,
+        OverflowPolicy::CoalesceRepeats,
+    ];
+
+    /// Reference model of the ring for a single thread.
+    struct Model {
+        queue: VecDeque<ButtonEvent>,
+        last: Option<ButtonEvent>,
+        overflows: u32,
+        capacity: usize,
+        policy: OverflowPolicy,
+    }
+
+    impl Model {
+        fn push(&mut self, event: ButtonEvent) -> PushResult {
+            let last = self.last.replace(event);
+            if self.queue.len() < self.capacity {
+                self.queue.push_back(event);
+                return PushResult::Queued;
+            }
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    self.queue.pop_front();
+                    self.queue.push_back(event);
+                    self.overflows += 1;
+                    PushResult::DroppedOldest
+                }
+                OverflowPolicy::CoalesceRepeats if last == Some(event) => PushResult::Coalesced,
+                _ => {
+                    self.overflows += 1;
+                    PushResult::DroppedNewest
+                }
+            }
+        }
+    }
+
+    /// Checks every sequence of `depth` operations against the model.
+    fn exhaust<const N: usize>(policy: OverflowPolicy, depth: u32) {
+        for mut code in 0..3usize.pow(depth) {
+            let ring = EventRing::<N>::new(policy);
+            let (mut tx, mut rx) = ring.split().unwrap();
+            let mut model = Model {
+                queue: VecDeque::new(),
+                last: None,
+                overflows: 0,
+                capacity: N,
+                policy,
+            };
+            for _ in 0..depth {
+                match code % 3 {
+                    0 => assert_eq!(tx.push(Pressed), model.push(Pressed)),
+                    1 => assert_eq!(tx.push(Released), model.push(Released)),
+                    _ => assert_eq!(rx.pop(), model.queue.pop_front()),
+                }
+                code /= 3;
+                assert_eq!(ring.len(), model.queue.len());
+                assert_eq!(ring.overflows(), model.overflows);
+            }
+        }
+    }
+
+    // ==================== Sequential Tests ====================
+
+    #[test]
+    fn test_split_only_once() {
+        let ring = EventRing::<4>::new(OverflowPolicy::DropNewest);
+        assert!(ring.split().is_some());
+        assert!(ring.split().is_none());
+    }
+
+    #[test]
+    fn test_fifo_order() {
+        let ring = EventRing::<4>::new(OverflowPolicy::DropNewest);
+        let (mut tx, mut rx) = ring.split().unwrap();
+        assert!(ring.is_empty());
+        tx.push(Pressed);
+        tx.push(Released);
+        assert_eq!(ring.len(), 2);
+        assert_eq!(rx.pop(), Some(Pressed));
+        assert_eq!(rx.pop(), Some(Released));
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_drop_newest_keeps_queue() {
+        let ring = EventRing::<2>::new(OverflowPolicy::DropNewest);
+        let (mut tx, mut rx) = ring.split().unwrap();
+        tx.push(Pressed);
+        tx.push(Released);
+        assert_eq!(tx.push(Pressed), PushResult::DroppedNewest);
+        assert_eq!(ring.overflows(), 1);
+        assert_eq!(rx.pop(), Some(Pressed));
+        assert_eq!(rx.pop(), Some(Released));
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_latest() {
+        let ring = EventRing::<2>::new(OverflowPolicy::DropOldest);
+        let (mut tx, mut rx) = ring.split().unwrap();
+        tx.push(Pressed);
+        tx.push(Released);
+        assert_eq!(tx.push(Pressed), PushResult::DroppedOldest);
+        assert_eq!(ring.overflows(), 1);
+        assert_eq!(rx.pop(), Some(Released));
+        assert_eq!(rx.pop(), Some(Pressed));
+    }
+
+    #[test]
+    fn test_coalesce_absorbs_repeat_only() {
+        let ring = EventRing::<2>::new(OverflowPolicy::CoalesceRepeats);
+        let (mut tx, _rx) = ring.split().unwrap();
+        tx.push(Pressed);
+        tx.push(Released);
+        assert_eq!(tx.push(Released), PushResult::Coalesced);
+        assert_eq!(ring.overflows(), 0);
+        assert_eq!(tx.push(Pressed), PushResult::DroppedNewest);
+        assert_eq!(ring.overflows(), 1);
+        assert_eq!(ring.policy(), OverflowPolicy::CoalesceRepeats);
+    }
+
+    #[test]
+    fn test_repeat_queued_when_not_full() {
+        let ring = EventRing::<4>::new(OverflowPolicy::CoalesceRepeats);
+        let (mut tx, _rx) = ring.split().unwrap();
+        tx.push(Pressed);
+        assert_eq!(tx.push(Pressed), PushResult::Queued);
+        assert_eq!(ring.len(), 2);
+    }
+
+    #[test]
+    fn test_drop_oldest_long_run_keeps_latest() {
+        let ring = EventRing::<2>::new(OverflowPolicy::DropOldest);
+        let (mut tx, mut rx) = ring.split().unwrap();
+        for _ in 0..1000 {
+            tx.push(Pressed);
+        }
+        tx.push(Released);
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.overflows(), 999);
+        assert_eq!(rx.pop(), Some(Pressed));
+        assert_eq!(rx.pop(), Some(Released));
+        assert!(ring.is_empty());
+    }
+
+    // ==================== Exhaustive Tests ====================
+
+    #[test]
+    fn test_exhaustive_against_model_capacity_2() {
+        for policy in POLICIES {
+            exhaust::<2>(policy, 9);
+        }
+    }
+
+    #[test]
+    fn test_exhaustive_against_model_capacity_4() {
+        for policy in POLICIES {
+            exhaust::<4>(policy, 10);
+        }
+    }
+
+    // ==================== Concurrency Tests ====================
+
+    /// Event counts from one producer/consumer race.
+    struct Race {
+        queued: usize,
+        popped: usize,
+        remaining: usize,
+        overflows: u32,
+        coalesced: usize,
+        rejected: usize,
+    }
+
+    /// Pushes alternating events from a thread while popping here.
+    fn race(policy: OverflowPolicy, count: usize) -> Race {
+        let ring: &'static EventRing<4> = Box::leak(Box::new(EventRing::new(policy)));
+        let (mut tx, mut rx) = ring.split().unwrap();
+        let producer = thread::spawn(move || {
+            let (mut queued, mut coalesced, mut rejected) = (0, 0, 0);
+            for i in 0..count {
+                let event = if i % 3 == 0 { Pressed } else { Released };
+                match tx.push(event) {
+                    PushResult::Queued | PushResult::DroppedOldest => queued += 1,
+                    PushResult::Coalesced => coalesced += 1,
+                    PushResult::DroppedNewest => rejected += 1,
+                }
+                if i % 7 == 0 {
+                    thread::yield_now();
+                }
+            }
+            (queued, coalesced, rejected)
+        });
+        let mut popped = 0;
+        while !producer.is_finished() {
+            if rx.pop().is_some() {
+                popped += 1;
+            } else {
+                thread::yield_now();
+            }
+            assert!(ring.len() <= 4);
+        }
+        let (queued, coalesced, rejected) = producer.join().unwrap();
+        Race {
+            queued,
+            popped,
+            remaining: ring.len(),
+            overflows: ring.overflows(),
+            coalesced,
+            rejected,
+        }
+    }
+
+    #[test]
+    fn test_threaded_drop_oldest_conserves_events() {
+        let r = race(OverflowPolicy::DropOldest, 50_000);
+        let dropped_oldest = r.overflows as usize - r.rejected;
+        assert_eq!(r.queued, r.popped + r.remaining + dropped_oldest);
+        assert_eq!(r.queued + r.rejected, 50_000);
+    }
+
+    #[test]
+    fn test_threaded_drop_newest_conserves_events() {
+        let r = race(OverflowPolicy::DropNewest, 50_000);
+        assert_eq!(r.queued, r.popped + r.remaining);
+        assert_eq!(r.queued + r.overflows as usize, 50_000);
+    }
+
+    #[test]
+    fn test_threaded_coalesce_conserves_events() {
+        let r = race(OverflowPolicy::CoalesceRepeats, 50_000);
+        assert_eq!(r.queued, r.popped + r.remaining);
+        assert_eq!(r.queued + r.overflows as usize + r.coalesced, 50_000);
+    }
+}
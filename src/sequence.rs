@@ -0,0 +1,429 @@
+/*
+ * @file sequence.rs
+ * @brief Press-sequence lock with timeout and lockout
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: sequence.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Button Sequence Lock.
+//!
+//! BRIEF:
+//! Matches short and long presses against a secret sequence, such as
+//! short-short-long-short, to unlock a function. Partial entries time
+//! out, repeated failures lock the input out, and the comparison runs
+//! in constant time.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::ButtonEvent;
+use crate::config::{
+    LONG_PRESS_MS, SEQUENCE_LOCKOUT_MS, SEQUENCE_MAX_FAILURES, SEQUENCE_TIMEOUT_MS,
+};
+
+/// Press gesture classified by duration.
+///
+/// # Variants
+/// * `Short` - Press shorter than the long-press threshold
+/// * `Long` - Press held at least the long-press threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum Gesture {
+    Short,
+    Long,
+}
+
+/// Sequence lock outcome.
+///
+/// # Variants
+/// * `Unlocked` - The entered sequence matched
+/// * `Rejected` - The entered sequence did not match
+/// * `LockedOut` - Too many failures; input ignored for a while
+/// * `TimedOut` - A partial sequence was discarded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum LockEvent {
+    Unlocked,
+    Rejected,
+    LockedOut,
+    TimedOut,
+}
+
+/// Sequence lock configuration.
+///
+/// # Fields
+/// * `long_press_ms` - Press duration that counts as a long press
+/// * `timeout_ms` - Maximum pause between presses
+/// * `max_failures` - Wrong sequences allowed before lockout
+/// * `lockout_ms` - Lockout duration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct SequenceConfig {
+    pub long_press_ms: u64,
+    pub timeout_ms: u64,
+    pub max_failures: u32,
+    pub lockout_ms: u64,
+}
+
+impl Default for SequenceConfig {
+    /// Returns configuration from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default sequence configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            long_press_ms: LONG_PRESS_MS,
+            timeout_ms: SEQUENCE_TIMEOUT_MS,
+            max_failures: SEQUENCE_MAX_FAILURES,
+            lockout_ms: SEQUENCE_LOCKOUT_MS,
+        }
+    }
+}
+
+/// Compares gesture sequences in constant time.
+///
+/// # Details
+/// Examines every element regardless of where the first mismatch is,
+/// so timing does not reveal how much of a guess was correct.
+///
+/// # Arguments
+/// * `a` - First sequence
+/// * `b` - Second sequence
+///
+/// # Returns
+/// * `bool` - true if lengths and all elements are equal
+#[allow(dead_code)]
+pub fn constant_time_eq(a: &[Gesture], b: &[Gesture]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |diff, (&x, &y)| diff | ((x as u8) ^ (y as u8)));
+    core::hint::black_box(diff) == 0
+}
+
+/// Press-sequence lock.
+///
+/// # Details
+/// Consumes debounced events from `ButtonController`. Once `N`
+/// gestures have been entered they are compared with the target as a
+/// whole, so a wrong guess is only reported after the full length.
+///
+/// # Fields
+/// * `config` - Lock configuration
+/// * `target` - Secret gesture sequence
+/// * `entered` - Gestures entered so far
+/// * `len` - Number of gestures entered
+/// * `press_start_ms` - Start of the press in progress
+/// * `last_ms` - End of the most recent press
+/// * `failures` - Consecutive wrong sequences
+/// * `locked_until_ms` - End of the current lockout, if any
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct SequenceLock<const N: usize> {
+    config: SequenceConfig,
+    target: [Gesture; N],
+    entered: [Gesture; N],
+    len: usize,
+    press_start_ms: Option<u64>,
+    last_ms: u64,
+    failures: u32,
+    locked_until_ms: Option<u64>,
+}
+
+impl<const N: usize> SequenceLock<N> {
+    /// Creates sequence lock.
+    ///
+    /// # Arguments
+    /// * `config` - Lock configuration
+    /// * `target` - Secret gesture sequence
+    ///
+    /// # Returns
+    /// * `Self` - New SequenceLock instance
+    #[allow(dead_code)]
+    pub fn new(config: SequenceConfig, target: [Gesture; N]) -> Self {
+        const { assert!(N > 0, "sequence must not be empty") };
+        Self {
+            config,
+            target,
+            entered: [Gesture::Short; N],
+            len: 0,
+            press_start_ms: None,
+            last_ms: 0,
+            failures: 0,
+            locked_until_ms: None,
+        }
+    }
+
+    /// Advances the lock.
+    ///
+    /// # Details
+    /// Call on every sample so partial sequences time out. Presses that
+    /// start during a lockout are ignored.
+    ///
+    /// # Arguments
+    /// * `event` - Debounced button event from this sample, if any
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<LockEvent>` - Outcome, if any
+    #[allow(dead_code)]
+    pub fn update(&mut self, event: Option<ButtonEvent>, now_ms: u64) -> Option<LockEvent> {
+        if let Some(until) = self.locked_until_ms {
+            if now_ms < until {
+                self.press_start_ms = None;
+                return None;
+            }
+            self.locked_until_ms = None;
+            self.failures = 0;
+        }
+        match event {
+            Some(ButtonEvent::Pressed) => {
+                self.press_start_ms = Some(now_ms);
+                None
+            }
+            Some(ButtonEvent::Released) => {
+                let start = self.press_start_ms.take()?;
+                self.entered[self.len] =
+                    if now_ms.saturating_sub(start) >= self.config.long_press_ms {
+                        Gesture::Long
+                    } else {
+                        Gesture::Short
+                    };
+                self.len += 1;
+                self.last_ms = now_ms;
+                (self.len == N).then(|| self.check(now_ms))
+            }
+            None => {
+                let idle = now_ms.saturating_sub(self.last_ms);
+                if self.len > 0 && self.press_start_ms.is_none() && idle >= self.config.timeout_ms {
+                    self.clear();
+                    return Some(LockEvent::TimedOut);
+                }
+                None
+            }
+        }
+    }
+
+    /// Returns true while input is locked out.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `bool` - true if locked out
+    #[allow(dead_code)]
+    pub fn is_locked_out(&self, now_ms: u64) -> bool {
+        self.locked_until_ms.is_some_and(|until| now_ms < until)
+    }
+
+    /// Returns number of gestures entered so far.
+    ///
+    /// # Returns
+    /// * `usize` - Entered gesture count
+    #[allow(dead_code)]
+    pub fn entered(&self) -> usize {
+        self.len
+    }
+
+    /// Returns number of consecutive wrong sequences.
+    ///
+    /// # Returns
+    /// * `u32` - Failure count
+    #[allow(dead_code)]
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Compares a complete entry and updates failure state.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `LockEvent` - Unlocked, Rejected or LockedOut
+    fn check(&mut self, now_ms: u64) -> LockEvent {
+        let matched = constant_time_eq(&self.entered, &self.target);
+        self.clear();
+        if matched {
+            self.failures = 0;
+            return LockEvent::Unlocked;
+        }
+        self.failures = self.failures.saturating_add(1);
+        if self.failures < self.config.max_failures {
+            return LockEvent::Rejected;
+        }
+        warn!("sequence: locked out after {} failures", self.failures);
+        self.locked_until_ms = Some(now_ms.saturating_add(self.config.lockout_ms));
+        LockEvent::LockedOut
+    }
+
+    /// Discards the entry in progress.
+    fn clear(&mut self) {
+        self.entered = [Gesture::Short; N];
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Gesture::{Long, Short};
+
+    const SECRET: [Gesture; 4] = [Short, Short, Long, Short];
+
+    fn lock() -> SequenceLock<4> {
+        SequenceLock::new(
+            SequenceConfig {
+                long_press_ms: 500,
+                timeout_ms: 2000,
+                max_failures: 2,
+                lockout_ms: 10_000,
+            },
+            SECRET,
+        )
+    }
+
+    /// Enters gestures starting at `t`; returns outcomes and end time.
+    fn enter(
+        lock: &mut SequenceLock<4>,
+        gestures: &[Gesture],
+        mut t: u64,
+    ) -> (Vec<LockEvent>, u64) {
+        let mut outcomes = Vec::new();
+        for &gesture in gestures {
+            outcomes.extend(lock.update(Some(ButtonEvent::Pressed), t));
+            t += if gesture == Long { 800 } else { 100 };
+            outcomes.extend(lock.update(Some(ButtonEvent::Released), t));
+            t += 200;
+        }
+        (outcomes, t)
+    }
+
+    // ==================== Comparison Tests ====================
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(&SECRET, &SECRET));
+        assert!(!constant_time_eq(&SECRET, &[Short, Short, Long, Long]));
+        assert!(!constant_time_eq(&SECRET, &[Long, Short, Long, Short]));
+        assert!(!constant_time_eq(&SECRET, &SECRET[..3]));
+    }
+
+    // ==================== Matching Tests ====================
+
+    #[test]
+    fn test_correct_sequence_unlocks() {
+        let mut lock = lock();
+        let (outcomes, _) = enter(&mut lock, &SECRET, 0);
+        assert_eq!(outcomes, [LockEvent::Unlocked]);
+        assert_eq!(lock.entered(), 0);
+    }
+
+    #[test]
+    fn test_wrong_sequence_rejected_after_full_length() {
+        let mut lock = lock();
+        let (outcomes, _) = enter(&mut lock, &[Long, Short, Long], 0);
+        assert!(outcomes.is_empty());
+        let (outcomes, _) = enter(&mut lock, &[Short], 5000);
+        assert_eq!(outcomes, [LockEvent::Rejected]);
+        assert_eq!(lock.failures(), 1);
+    }
+
+    #[test]
+    fn test_success_clears_failures() {
+        let mut lock = lock();
+        let (_, t) = enter(&mut lock, &[Long; 4], 0);
+        let (outcomes, _) = enter(&mut lock, &SECRET, t);
+        assert_eq!(outcomes, [LockEvent::Unlocked]);
+        assert_eq!(lock.failures(), 0);
+    }
+
+    // ==================== Timeout Tests ====================
+
+    #[test]
+    fn test_partial_sequence_times_out() {
+        let mut lock = lock();
+        let (_, t) = enter(&mut lock, &SECRET[..2], 0);
+        assert_eq!(lock.update(None, t + 1000), None);
+        assert_eq!(lock.update(None, t + 1800), Some(LockEvent::TimedOut));
+        assert_eq!(lock.entered(), 0);
+        let (outcomes, _) = enter(&mut lock, &SECRET, t + 2000);
+        assert_eq!(outcomes, [LockEvent::Unlocked]);
+    }
+
+    #[test]
+    fn test_no_timeout_while_held() {
+        let mut lock = lock();
+        let (_, t) = enter(&mut lock, &SECRET[..1], 0);
+        lock.update(Some(ButtonEvent::Pressed), t);
+        assert_eq!(lock.update(None, t + 5000), None);
+    }
+
+    // ==================== Lockout Tests ====================
+
+    #[test]
+    fn test_lockout_after_max_failures() {
+        let mut lock = lock();
+        let (_, t) = enter(&mut lock, &[Long; 4], 0);
+        let (outcomes, t) = enter(&mut lock, &[Long; 4], t);
+        assert_eq!(outcomes, [LockEvent::LockedOut]);
+        assert!(lock.is_locked_out(t));
+        let (outcomes, _) = enter(&mut lock, &SECRET, t);
+        assert!(outcomes.is_empty());
+    }
+
+    #[test]
+    fn test_lockout_expires() {
+        let mut lock = lock();
+        let (_, t) = enter(&mut lock, &[Long; 4], 0);
+        let (_, t) = enter(&mut lock, &[Long; 4], t);
+        let after = t + 10_000;
+        assert!(!lock.is_locked_out(after));
+        let (outcomes, _) = enter(&mut lock, &SECRET, after);
+        assert_eq!(outcomes, [LockEvent::Unlocked]);
+    }
+
+    #[test]
+    fn test_release_of_press_started_in_lockout_ignored() {
+        let mut lock = lock();
+        let (_, t) = enter(&mut lock, &[Long; 4], 0);
+        let (_, t) = enter(&mut lock, &[Long; 4], t);
+        lock.update(Some(ButtonEvent::Pressed), t + 9_500);
+        lock.update(Some(ButtonEvent::Released), t + 10_200);
+        assert_eq!(lock.entered(), 0);
+    }
+}
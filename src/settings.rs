@@ -0,0 +1,837 @@
+/*
+ * @file settings.rs
+ * @brief Flash-backed settings and counter persistence
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: settings.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Flash-Backed Settings Store.
+//!
+//! BRIEF:
+//! Encodes settings and press counters into versioned, CRC-protected
+//! records and appends them round-robin across the reserved flash
+//! sectors. Falls back to `config.rs` defaults when no valid record exists,
+//! and to the compiled debounce and LED settings when the firmware's
+//! defaults have changed since the record was written. Runtime changes
+//! go through the validating setters on `Settings` and are persisted
+//! with `SettingsStore::save`.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::config::{
+    BLINK_DELAY_MS, DEBOUNCE_COUNT, DEBOUNCE_DELAY_MS, FAULT_BLINK_MS, FAULT_PAUSE_MS,
+    FLASH_SECTOR_SIZE, LED_TICK_MS, MAX_DEBOUNCE_COUNT, SETTINGS_SLOT_SIZE,
+};
+use crate::stats::ButtonStats;
+
+/// Record magic number ("BTNS" little-endian).
+const RECORD_MAGIC: u32 = 0x534E_5442;
+
+/// Current record format version.
+const RECORD_VERSION: u8 = 3;
+
+/// Encoded record length in bytes.
+///
+/// # Details
+/// Magic (4) + version/reserved (4) + sequence (4) + config ID (4) +
+/// payload (36) + CRC (4).
+pub const RECORD_SIZE: usize = 56;
+
+/// Largest LED tick accepted from a record, matching `button.toml`.
+const MAX_LED_TICK_MS: u32 = 1000;
+
+/// Compiled defaults covered by `CONFIG_ID`, in record order.
+const CONFIG_DEFAULTS: [u32; 6] = [
+    DEBOUNCE_DELAY_MS as u32,
+    DEBOUNCE_COUNT,
+    BLINK_DELAY_MS as u32,
+    LED_TICK_MS as u32,
+    FAULT_BLINK_MS,
+    FAULT_PAUSE_MS,
+];
+
+/// Identifier of the compiled debounce and LED defaults.
+///
+/// # Details
+/// CRC-32 of the little-endian `CONFIG_DEFAULTS`, written into every
+/// record. A record from a build with different defaults keeps its
+/// counters but not its debounce or LED settings, so a rebuilt
+/// firmware (or a new `button.toml`) takes effect.
+#[allow(dead_code)]
+pub const CONFIG_ID: u32 = {
+    let mut bytes = [0u8; CONFIG_DEFAULTS.len() * 4];
+    let mut i = 0;
+    while i < bytes.len() {
+        bytes[i] = CONFIG_DEFAULTS[i / 4].to_le_bytes()[i % 4];
+        i += 1;
+    }
+    crc32(&bytes)
+};
+
+/// Minimal flash interface required by the settings store.
+///
+/// # Details
+/// Offsets are relative to the start of flash. Erase ranges are
+/// sector-aligned. Implemented by the firmware for the RP2350 flash
+/// and by an in-memory mock for host tests.
+pub trait SettingsFlash {
+    /// Error type reported by the flash driver.
+    type Error;
+
+    /// Reads bytes starting at `offset`.
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Programs bytes starting at `offset` into erased flash.
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Erases the sector-aligned range `from..to`.
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error>;
+}
+
+/// Persisted runtime settings and lifetime counters.
+///
+/// # Details
+/// Plain data record written to flash by `SettingsStore`.
+///
+/// # Fields
+/// * `debounce_delay_ms` - Delay between button samples
+/// * `debounce_count` - Stable samples required for a state change
+/// * `blink_delay_ms` - LED controller blink delay
+/// * `led_tick_ms` - LED pattern update interval
+/// * `fault_blink_ms` - Fault pattern on and off time
+/// * `fault_pause_ms` - Pause between fault pattern bursts
+/// * `total_presses` - Lifetime press count
+/// * `long_presses` - Lifetime long-press count
+/// * `bounces_rejected` - Lifetime rejected bounce count
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct Settings {
+    pub debounce_delay_ms: u32,
+    pub debounce_count: u32,
+    pub blink_delay_ms: u32,
+    pub led_tick_ms: u32,
+    pub fault_blink_ms: u32,
+    pub fault_pause_ms: u32,
+    pub total_presses: u32,
+    pub long_presses: u32,
+    pub bounces_rejected: u32,
+}
+
+impl Default for Settings {
+    /// Returns settings built from `config.rs` defaults.
+    ///
+    /// # Returns
+    /// * `Self` - Default settings with zeroed counters
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            debounce_delay_ms: DEBOUNCE_DELAY_MS as u32,
+            debounce_count: DEBOUNCE_COUNT,
+            blink_delay_ms: BLINK_DELAY_MS as u32,
+            led_tick_ms: LED_TICK_MS as u32,
+            fault_blink_ms: FAULT_BLINK_MS,
+            fault_pause_ms: FAULT_PAUSE_MS,
+            total_presses: 0,
+            long_presses: 0,
+            bounces_rejected: 0,
+        }
+    }
+}
+
+impl Settings {
+    /// Returns true if all settings are within usable ranges.
+    ///
+    /// # Details
+    /// Applies the same timing rules `build.rs` enforces on
+    /// `button.toml`: the blink delay outlasts a debounce sample and
+    /// the fault pattern is no finer than the LED tick.
+    ///
+    /// # Returns
+    /// * `bool` - true if the settings may be applied
+    #[allow(dead_code)]
+    pub fn is_valid(&self) -> bool {
+        self.debounce_delay_ms > 0
+            && (1..=MAX_DEBOUNCE_COUNT).contains(&self.debounce_count)
+            && self.blink_delay_ms > self.debounce_delay_ms
+            && (1..=MAX_LED_TICK_MS).contains(&self.led_tick_ms)
+            && self.fault_blink_ms > self.led_tick_ms
+            && self.fault_pause_ms >= self.led_tick_ms
+    }
+
+    /// Returns a copy with new LED timing.
+    ///
+    /// # Arguments
+    /// * `blink_delay_ms` - LED controller blink delay
+    /// * `led_tick_ms` - LED pattern update interval
+    /// * `fault_blink_ms` - Fault pattern on and off time
+    /// * `fault_pause_ms` - Pause between fault pattern bursts
+    ///
+    /// # Returns
+    /// * `Option<Settings>` - Updated settings, or None if out of range
+    #[allow(dead_code)]
+    pub fn with_led_timing(
+        &self,
+        blink_delay_ms: u32,
+        led_tick_ms: u32,
+        fault_blink_ms: u32,
+        fault_pause_ms: u32,
+    ) -> Option<Settings> {
+        let settings = Settings {
+            blink_delay_ms,
+            led_tick_ms,
+            fault_blink_ms,
+            fault_pause_ms,
+            ..*self
+        };
+        settings.is_valid().then_some(settings)
+    }
+
+    /// Returns a copy with counters taken from live statistics.
+    ///
+    /// # Arguments
+    /// * `stats` - Current button statistics
+    ///
+    /// # Returns
+    /// * `Settings` - Settings with updated counters
+    #[allow(dead_code)]
+    pub fn with_stats(&self, stats: &ButtonStats) -> Settings {
+        Settings {
+            total_presses: stats.total_presses(),
+            long_presses: stats.long_presses(),
+            bounces_rejected: stats.bounces_rejected(),
+            ..*self
+        }
+    }
+
+    /// Returns button statistics seeded from the stored counters.
+    ///
+    /// # Returns
+    /// * `ButtonStats` - Restored statistics
+    #[allow(dead_code)]
+    pub fn stats(&self) -> ButtonStats {
+        ButtonStats::restore(self.total_presses, self.long_presses, self.bounces_rejected)
+    }
+}
+
+/// Computes CRC-32 (IEEE 802.3) of a byte slice.
+///
+/// # Arguments
+/// * `bytes` - Data to checksum
+///
+/// # Returns
+/// * `u32` - CRC-32 value
+#[allow(dead_code)]
+pub const fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    let mut i = 0;
+    while i < bytes.len() {
+        crc ^= bytes[i] as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+            bit += 1;
+        }
+        i += 1;
+    }
+    !crc
+}
+
+/// Encodes settings into a flash record.
+///
+/// # Arguments
+/// * `settings` - Settings to encode
+/// * `sequence` - Monotonic record sequence number
+///
+/// # Returns
+/// * `[u8; RECORD_SIZE]` - Encoded record
+#[allow(dead_code)]
+pub fn encode_record(settings: &Settings, sequence: u32) -> [u8; RECORD_SIZE] {
+    encode_record_for(settings, sequence, CONFIG_ID)
+}
+
+/// Encodes settings into a flash record tagged with a config ID.
+///
+/// # Arguments
+/// * `settings` - Settings to encode
+/// * `sequence` - Monotonic record sequence number
+/// * `config_id` - Identifier of the defaults the record was written under
+///
+/// # Returns
+/// * `[u8; RECORD_SIZE]` - Encoded record
+fn encode_record_for(settings: &Settings, sequence: u32, config_id: u32) -> [u8; RECORD_SIZE] {
+    let mut buf = [0u8; RECORD_SIZE];
+    let words = [
+        RECORD_MAGIC,
+        RECORD_VERSION as u32,
+        sequence,
+        config_id,
+        settings.debounce_delay_ms,
+        settings.debounce_count,
+        settings.blink_delay_ms,
+        settings.led_tick_ms,
+        settings.fault_blink_ms,
+        settings.fault_pause_ms,
+        settings.total_presses,
+        settings.long_presses,
+        settings.bounces_rejected,
+    ];
+    for (chunk, word) in buf.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    let crc = crc32(&buf[..RECORD_SIZE - 4]);
+    buf[RECORD_SIZE - 4..].copy_from_slice(&crc.to_le_bytes());
+    buf
+}
+
+/// Decodes a flash record.
+///
+/// # Details
+/// Rejects records with wrong magic, unknown version, bad CRC or
+/// out-of-range settings. A record written under a different
+/// `CONFIG_ID` keeps its counters and takes the compiled debounce and
+/// LED settings.
+///
+/// # Arguments
+/// * `buf` - Raw record bytes
+///
+/// # Returns
+/// * `Option<(Settings, u32)>` - Settings and sequence number if valid
+#[allow(dead_code)]
+pub fn decode_record(buf: &[u8; RECORD_SIZE]) -> Option<(Settings, u32)> {
+    let word =
+        |i: usize| u32::from_le_bytes([buf[i * 4], buf[i * 4 + 1], buf[i * 4 + 2], buf[i * 4 + 3]]);
+    if word(0) != RECORD_MAGIC || word(1) != RECORD_VERSION as u32 {
+        return None;
+    }
+    if word(RECORD_SIZE / 4 - 1) != crc32(&buf[..RECORD_SIZE - 4]) {
+        return None;
+    }
+    let stored = Settings {
+        debounce_delay_ms: word(4),
+        debounce_count: word(5),
+        blink_delay_ms: word(6),
+        led_tick_ms: word(7),
+        fault_blink_ms: word(8),
+        fault_pause_ms: word(9),
+        total_presses: word(10),
+        long_presses: word(11),
+        bounces_rejected: word(12),
+    };
+    if word(3) != CONFIG_ID {
+        info!("settings: defaults changed, keeping counters only");
+        let counters = stored.stats();
+        return Some((Settings::default().with_stats(&counters), word(2)));
+    }
+    stored.is_valid().then_some((stored, word(2)))
+}
+
+/// Wear-levelled settings store.
+///
+/// # Details
+/// Appends each save to the next slot of a ring spanning
+/// `sector_count` flash sectors, erasing a sector only when the ring
+/// enters it. The newest valid record wins on load.
+///
+/// # Fields
+/// * `flash` - Flash driver
+/// * `base` - Flash offset of the first reserved sector
+/// * `sector_count` - Number of reserved sectors
+/// * `latest` - Slot index and sequence of the newest valid record
+pub struct SettingsStore<F: SettingsFlash> {
+    flash: F,
+    base: u32,
+    sector_count: u32,
+    latest: Option<(u32, u32)>,
+}
+
+impl<F: SettingsFlash> SettingsStore<F> {
+    /// Creates a settings store over the reserved flash sectors.
+    ///
+    /// # Arguments
+    /// * `flash` - Flash driver
+    /// * `base` - Sector-aligned flash offset of the reserved region
+    /// * `sector_count` - Number of reserved sectors (at least one)
+    ///
+    /// # Returns
+    /// * `Self` - New SettingsStore instance
+    #[allow(dead_code)]
+    pub fn new(flash: F, base: u32, sector_count: u32) -> Self {
+        Self {
+            flash,
+            base,
+            sector_count: sector_count.max(1),
+            latest: None,
+        }
+    }
+
+    /// Loads the newest valid settings record.
+    ///
+    /// # Details
+    /// Scans every slot and keeps the valid record with the highest
+    /// sequence number. Returns defaults when none is found.
+    ///
+    /// # Returns
+    /// * `Settings` - Stored settings or `config.rs` defaults
+    #[allow(dead_code)]
+    pub fn load(&mut self) -> Settings {
+        let mut best: Option<(Settings, u32, u32)> = None;
+        for slot in 0..self.slot_count() {
+            let Some((settings, seq)) = self.read_slot(slot) else {
+                continue;
+            };
+            if best.is_none_or(|(_, _, best_seq)| seq > best_seq) {
+                best = Some((settings, slot, seq));
+            }
+        }
+        self.latest = best.map(|(_, slot, seq)| (slot, seq));
+        match best {
+            Some((settings, _, _)) => settings,
+            None => {
+                warn!("settings: no valid record, using defaults");
+                Settings::default()
+            }
+        }
+    }
+
+    /// Appends a settings record to flash.
+    ///
+    /// # Details
+    /// Writes to the slot after the newest record, erasing the sector
+    /// when entering it. Skips to the next sector if the target slot
+    /// is not blank.
+    ///
+    /// # Arguments
+    /// * `settings` - Settings to persist
+    ///
+    /// # Returns
+    /// * `Result<(), F::Error>` - Flash driver error on failure
+    #[allow(dead_code)]
+    pub fn save(&mut self, settings: &Settings) -> Result<(), F::Error> {
+        let (mut slot, seq) = match self.latest {
+            Some((slot, seq)) => ((slot + 1) % self.slot_count(), seq.wrapping_add(1)),
+            None => (0, 0),
+        };
+        if slot % self.slots_per_sector() != 0 && !self.slot_is_blank(slot)? {
+            slot = self.next_sector_start(slot);
+        }
+        if slot % self.slots_per_sector() == 0 {
+            let from = self.slot_offset(slot);
+            self.flash.erase(from, from + FLASH_SECTOR_SIZE)?;
+        }
+        let record = encode_record(settings, seq);
+        self.flash.write(self.slot_offset(slot), &record)?;
+        self.latest = Some((slot, seq));
+        debug!("settings: saved seq {} to slot {}", seq, slot);
+        Ok(())
+    }
+
+    /// Releases the underlying flash driver.
+    ///
+    /// # Returns
+    /// * `F` - Flash driver
+    #[allow(dead_code)]
+    pub fn into_inner(self) -> F {
+        self.flash
+    }
+
+    /// Returns number of record slots per sector.
+    fn slots_per_sector(&self) -> u32 {
+        FLASH_SECTOR_SIZE / SETTINGS_SLOT_SIZE
+    }
+
+    /// Returns total number of record slots.
+    fn slot_count(&self) -> u32 {
+        self.slots_per_sector() * self.sector_count
+    }
+
+    /// Returns flash offset of a slot.
+    fn slot_offset(&self, slot: u32) -> u32 {
+        self.base + slot * SETTINGS_SLOT_SIZE
+    }
+
+    /// Returns first slot of the sector following `slot`.
+    fn next_sector_start(&self, slot: u32) -> u32 {
+        let per_sector = self.slots_per_sector();
+        ((slot / per_sector + 1) * per_sector) % self.slot_count()
+    }
+
+    /// Reads and decodes a slot, treating read errors as empty.
+    fn read_slot(&mut self, slot: u32) -> Option<(Settings, u32)> {
+        let mut buf = [0u8; RECORD_SIZE];
+        self.flash.read(self.slot_offset(slot), &mut buf).ok()?;
+        decode_record(&buf)
+    }
+
+    /// Returns true if a slot is fully erased.
+    fn slot_is_blank(&mut self, slot: u32) -> Result<bool, F::Error> {
+        let mut buf = [0u8; RECORD_SIZE];
+        self.flash.read(self.slot_offset(slot), &mut buf)?;
+        Ok(buf.iter().all(|&b| b == 0xFF))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTORS: u32 = 3;
+    const BASE: u32 = 2 * FLASH_SECTOR_SIZE;
+    const SIZE: usize = ((2 + SECTORS) * FLASH_SECTOR_SIZE) as usize;
+
+    /// In-memory NOR flash mock: writes can only clear bits.
+    struct MockFlash {
+        data: Vec<u8>,
+        erases: Vec<u32>,
+    }
+
+    impl MockFlash {
+        fn new() -> Self {
+            Self {
+                data: vec![0xFF; SIZE],
+                erases: Vec::new(),
+            }
+        }
+    }
+
+    impl SettingsFlash for MockFlash {
+        type Error = ();
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ()> {
+            let start = offset as usize;
+            bytes.copy_from_slice(self.data.get(start..start + bytes.len()).ok_or(())?);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ()> {
+            let start = offset as usize;
+            let dest = self.data.get_mut(start..start + bytes.len()).ok_or(())?;
+            for (d, s) in dest.iter_mut().zip(bytes) {
+                *d &= *s;
+            }
+            Ok(())
+        }
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), ()> {
+            assert_eq!(from % FLASH_SECTOR_SIZE, 0);
+            assert_eq!(to % FLASH_SECTOR_SIZE, 0);
+            self.data[from as usize..to as usize].fill(0xFF);
+            self.erases.push(from);
+            Ok(())
+        }
+    }
+
+    fn settings_with_presses(total_presses: u32) -> Settings {
+        Settings {
+            total_presses,
+            ..Settings::default()
+        }
+    }
+
+    fn slots_per_sector() -> u32 {
+        FLASH_SECTOR_SIZE / SETTINGS_SLOT_SIZE
+    }
+
+    // ==================== CRC Tests ====================
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_crc32_empty() {
+        assert_eq!(crc32(&[]), 0);
+    }
+
+    // ==================== Record Encoding Tests ====================
+
+    #[test]
+    fn test_record_round_trip() {
+        let settings = Settings {
+            debounce_delay_ms: 7,
+            debounce_count: 3,
+            blink_delay_ms: 250,
+            led_tick_ms: 20,
+            fault_blink_ms: 150,
+            fault_pause_ms: 900,
+            total_presses: 1234,
+            long_presses: 56,
+            bounces_rejected: 789,
+        };
+        let record = encode_record(&settings, 42);
+        assert_eq!(decode_record(&record), Some((settings, 42)));
+    }
+
+    #[test]
+    fn test_record_rejects_bad_crc() {
+        let mut record = encode_record(&Settings::default(), 1);
+        record[20] ^= 0x01;
+        assert_eq!(decode_record(&record), None);
+    }
+
+    #[test]
+    fn test_record_rejects_bad_magic() {
+        let mut record = encode_record(&Settings::default(), 1);
+        record[0] = 0;
+        assert_eq!(decode_record(&record), None);
+    }
+
+    #[test]
+    fn test_record_rejects_unknown_version() {
+        let mut record = encode_record(&Settings::default(), 1);
+        record[4] = RECORD_VERSION + 1;
+        let crc = crc32(&record[..RECORD_SIZE - 4]);
+        record[RECORD_SIZE - 4..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(decode_record(&record), None);
+    }
+
+    #[test]
+    fn test_record_rejects_invalid_settings() {
+        let settings = Settings {
+            debounce_count: 0,
+            ..Settings::default()
+        };
+        assert_eq!(decode_record(&encode_record(&settings, 1)), None);
+    }
+
+    #[test]
+    fn test_record_rejects_invalid_led_timing() {
+        let settings = Settings {
+            fault_blink_ms: Settings::default().led_tick_ms,
+            ..Settings::default()
+        };
+        assert_eq!(decode_record(&encode_record(&settings, 1)), None);
+    }
+
+    #[test]
+    fn test_record_from_other_defaults_keeps_counters_only() {
+        let settings = Settings {
+            debounce_delay_ms: 9,
+            debounce_count: 7,
+            blink_delay_ms: 300,
+            led_tick_ms: 5,
+            fault_blink_ms: 50,
+            fault_pause_ms: 400,
+            total_presses: 12,
+            long_presses: 3,
+            bounces_rejected: 40,
+        };
+        let record = encode_record_for(&settings, 8, CONFIG_ID ^ 1);
+        let (loaded, seq) = decode_record(&record).unwrap();
+        assert_eq!(seq, 8);
+        assert_eq!(loaded, Settings::default().with_stats(&settings.stats()));
+    }
+
+    #[test]
+    fn test_record_from_other_defaults_ignores_invalid_tunables() {
+        let settings = Settings {
+            debounce_count: 0,
+            total_presses: 5,
+            ..Settings::default()
+        };
+        let record = encode_record_for(&settings, 1, CONFIG_ID ^ 1);
+        assert_eq!(decode_record(&record).unwrap().0, settings_with_presses(5));
+    }
+
+    #[test]
+    fn test_config_id_tracks_defaults() {
+        let record = encode_record(&Settings::default(), 1);
+        assert_eq!(record[12..16], CONFIG_ID.to_le_bytes());
+        let bytes: Vec<u8> = [
+            DEBOUNCE_DELAY_MS as u32,
+            DEBOUNCE_COUNT,
+            BLINK_DELAY_MS as u32,
+            LED_TICK_MS as u32,
+            FAULT_BLINK_MS,
+            FAULT_PAUSE_MS,
+        ]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+        assert_eq!(CONFIG_ID, crc32(&bytes));
+    }
+
+    #[test]
+    fn test_erased_record_rejected() {
+        assert_eq!(decode_record(&[0xFF; RECORD_SIZE]), None);
+    }
+
+    #[test]
+    fn test_record_fits_slot() {
+        assert!(RECORD_SIZE as u32 <= SETTINGS_SLOT_SIZE);
+        assert_eq!(FLASH_SECTOR_SIZE % SETTINGS_SLOT_SIZE, 0);
+    }
+
+    // ==================== Settings Tests ====================
+
+    #[test]
+    fn test_default_settings_from_config() {
+        let settings = Settings::default();
+        assert_eq!(settings.debounce_delay_ms as u64, DEBOUNCE_DELAY_MS);
+        assert_eq!(settings.debounce_count, DEBOUNCE_COUNT);
+        assert_eq!(settings.blink_delay_ms as u64, BLINK_DELAY_MS);
+        assert_eq!(settings.led_tick_ms as u64, LED_TICK_MS);
+        assert_eq!(settings.fault_blink_ms, FAULT_BLINK_MS);
+        assert_eq!(settings.fault_pause_ms, FAULT_PAUSE_MS);
+        assert!(settings.is_valid());
+    }
+
+    #[test]
+    fn test_with_led_timing_validates() {
+        let settings = Settings::default();
+        let updated = settings.with_led_timing(400, 20, 200, 800).unwrap();
+        assert_eq!(updated.led_tick_ms, 20);
+        assert_eq!(updated.fault_pause_ms, 800);
+        assert_eq!(updated.stats(), settings.stats());
+        assert_eq!(settings.with_led_timing(400, 0, 200, 800), None);
+        assert_eq!(settings.with_led_timing(400, 20, 20, 800), None);
+        assert_eq!(settings.with_led_timing(400, 20, 200, 10), None);
+        let delay = settings.debounce_delay_ms;
+        assert_eq!(settings.with_led_timing(delay, 20, 200, 800), None);
+    }
+
+    #[test]
+    fn test_stats_round_trip() {
+        let settings = Settings {
+            total_presses: 9,
+            long_presses: 2,
+            bounces_rejected: 4,
+            ..Settings::default()
+        };
+        assert_eq!(Settings::default().with_stats(&settings.stats()), settings);
+    }
+
+    // ==================== Store Tests ====================
+
+    #[test]
+    fn test_load_blank_flash_returns_defaults() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        assert_eq!(store.load(), Settings::default());
+    }
+
+    #[test]
+    fn test_save_then_load() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        store.save(&settings_with_presses(5)).unwrap();
+        let mut reopened = SettingsStore::new(store.into_inner(), BASE, SECTORS);
+        assert_eq!(reopened.load(), settings_with_presses(5));
+    }
+
+    #[test]
+    fn test_led_timing_survives_reload() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        let changed = store.load().with_led_timing(250, 5, 50, 300).unwrap();
+        store.save(&changed).unwrap();
+        let mut reopened = SettingsStore::new(store.into_inner(), BASE, SECTORS);
+        assert_eq!(reopened.load(), changed);
+    }
+
+    #[test]
+    fn test_latest_record_wins() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        for n in 0..10 {
+            store.save(&settings_with_presses(n)).unwrap();
+        }
+        let mut reopened = SettingsStore::new(store.into_inner(), BASE, SECTORS);
+        assert_eq!(reopened.load(), settings_with_presses(9));
+    }
+
+    #[test]
+    fn test_corrupt_latest_falls_back_to_previous() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        store.save(&settings_with_presses(1)).unwrap();
+        store.save(&settings_with_presses(2)).unwrap();
+        let mut flash = store.into_inner();
+        flash.data[(BASE + SETTINGS_SLOT_SIZE + 12) as usize] ^= 0xFF;
+        let mut reopened = SettingsStore::new(flash, BASE, SECTORS);
+        assert_eq!(reopened.load(), settings_with_presses(1));
+    }
+
+    #[test]
+    fn test_wear_levelling_erases_each_sector_once_per_pass() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        for n in 0..slots_per_sector() * SECTORS {
+            store.save(&settings_with_presses(n)).unwrap();
+        }
+        let flash = store.into_inner();
+        let expected: Vec<u32> = (0..SECTORS).map(|s| BASE + s * FLASH_SECTOR_SIZE).collect();
+        assert_eq!(flash.erases, expected);
+    }
+
+    #[test]
+    fn test_ring_wraps_and_keeps_latest() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        let total = slots_per_sector() * SECTORS + 3;
+        for n in 0..total {
+            store.save(&settings_with_presses(n)).unwrap();
+        }
+        let flash = store.into_inner();
+        assert_eq!(flash.erases.len() as u32, SECTORS + 1);
+        let mut reopened = SettingsStore::new(flash, BASE, SECTORS);
+        assert_eq!(reopened.load(), settings_with_presses(total - 1));
+        reopened.save(&settings_with_presses(999)).unwrap();
+        let mut again = SettingsStore::new(reopened.into_inner(), BASE, SECTORS);
+        assert_eq!(again.load(), settings_with_presses(999));
+    }
+
+    #[test]
+    fn test_dirty_slot_skips_to_next_sector() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        store.save(&settings_with_presses(1)).unwrap();
+        let mut flash = store.into_inner();
+        flash.data[(BASE + SETTINGS_SLOT_SIZE) as usize] = 0x00;
+        let mut reopened = SettingsStore::new(flash, BASE, SECTORS);
+        assert_eq!(reopened.load(), settings_with_presses(1));
+        reopened.save(&settings_with_presses(2)).unwrap();
+        let flash = reopened.into_inner();
+        assert_eq!(flash.erases, vec![BASE, BASE + FLASH_SECTOR_SIZE]);
+        let mut again = SettingsStore::new(flash, BASE, SECTORS);
+        assert_eq!(again.load(), settings_with_presses(2));
+    }
+
+    #[test]
+    fn test_store_never_touches_outside_region() {
+        let mut store = SettingsStore::new(MockFlash::new(), BASE, SECTORS);
+        store.load();
+        for n in 0..slots_per_sector() * SECTORS * 2 {
+            store.save(&settings_with_presses(n)).unwrap();
+        }
+        let flash = store.into_inner();
+        assert!(flash.data[..BASE as usize].iter().all(|&b| b == 0xFF));
+    }
+}
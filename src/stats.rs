@@ -0,0 +1,564 @@
+/*
+ * @file stats.rs
+ * @brief Button press counters and usage statistics
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: stats.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Button Usage Statistics.
+//!
+//! BRIEF:
+//! Tracks press counts, press durations and rejected bounces.
+//! All counters use saturating arithmetic.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{ButtonController, ButtonEvent};
+use crate::config::LONG_PRESS_MS;
+use crate::fault::{Fault, FaultMonitor};
+
+/// Button usage statistics.
+///
+/// # Details
+/// Accumulates press counts and durations from debounced events.
+/// Timestamps are caller-supplied milliseconds from any monotonic clock.
+///
+/// # Fields
+/// * `total_presses` - Number of presses observed
+/// * `long_presses` - Number of presses held at least `LONG_PRESS_MS`
+/// * `completed_presses` - Number of presses followed by a release
+/// * `shortest_press_ms` - Shortest completed press duration
+/// * `longest_press_ms` - Longest completed press duration
+/// * `total_press_ms` - Sum of completed press durations
+/// * `bounces_rejected` - Bounces rejected by the debouncer
+/// * `last_press_ms` - Timestamp of the most recent press
+/// * `press_start_ms` - Timestamp of the press in progress
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct ButtonStats {
+    total_presses: u32,
+    long_presses: u32,
+    completed_presses: u32,
+    shortest_press_ms: Option<u64>,
+    longest_press_ms: Option<u64>,
+    total_press_ms: u64,
+    bounces_rejected: u32,
+    last_press_ms: Option<u64>,
+    press_start_ms: Option<u64>,
+}
+
+impl Default for ButtonStats {
+    /// Returns default ButtonStats instance.
+    ///
+    /// # Details
+    /// Delegates to new() for initialization.
+    ///
+    /// # Returns
+    /// * `Self` - New ButtonStats with all counters cleared
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ButtonStats {
+    /// Creates empty statistics.
+    ///
+    /// # Details
+    /// All counters start at zero and no press has been seen.
+    ///
+    /// # Returns
+    /// * `Self` - New ButtonStats instance
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self {
+            total_presses: 0,
+            long_presses: 0,
+            completed_presses: 0,
+            shortest_press_ms: None,
+            longest_press_ms: None,
+            total_press_ms: 0,
+            bounces_rejected: 0,
+            last_press_ms: None,
+            press_start_ms: None,
+        }
+    }
+
+    /// Creates statistics seeded with persisted counters.
+    ///
+    /// # Details
+    /// Restores lifetime counters; duration statistics start empty.
+    ///
+    /// # Arguments
+    /// * `total_presses` - Previously recorded press count
+    /// * `long_presses` - Previously recorded long-press count
+    /// * `bounces_rejected` - Previously recorded bounce count
+    ///
+    /// # Returns
+    /// * `Self` - New ButtonStats instance
+    #[allow(dead_code)]
+    pub fn restore(total_presses: u32, long_presses: u32, bounces_rejected: u32) -> Self {
+        Self {
+            total_presses,
+            long_presses,
+            bounces_rejected,
+            ..Self::new()
+        }
+    }
+
+    /// Records a debounced button event.
+    ///
+    /// # Details
+    /// Press starts a duration measurement; release completes it.
+    /// A release without a preceding press is ignored.
+    ///
+    /// # Arguments
+    /// * `event` - Debounced button event
+    /// * `now_ms` - Current timestamp in milliseconds
+    #[allow(dead_code)]
+    pub fn record(&mut self, event: ButtonEvent, now_ms: u64) {
+        match event {
+            ButtonEvent::Pressed => {
+                self.total_presses = self.total_presses.saturating_add(1);
+                self.last_press_ms = Some(now_ms);
+                self.press_start_ms = Some(now_ms);
+            }
+            ButtonEvent::Released => {
+                if let Some(start) = self.press_start_ms.take() {
+                    self.record_duration(now_ms.saturating_sub(start));
+                }
+            }
+        }
+    }
+
+    /// Adds rejected bounces to the running total.
+    ///
+    /// # Arguments
+    /// * `count` - Number of newly rejected bounces
+    #[allow(dead_code)]
+    pub fn record_bounces(&mut self, count: u32) {
+        self.bounces_rejected = self.bounces_rejected.saturating_add(count);
+    }
+
+    /// Returns total number of presses.
+    ///
+    /// # Returns
+    /// * `u32` - Press count
+    #[allow(dead_code)]
+    pub fn total_presses(&self) -> u32 {
+        self.total_presses
+    }
+
+    /// Returns number of long-presses.
+    ///
+    /// # Returns
+    /// * `u32` - Long-press count
+    #[allow(dead_code)]
+    pub fn long_presses(&self) -> u32 {
+        self.long_presses
+    }
+
+    /// Returns shortest completed press duration.
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Duration in milliseconds, None before first release
+    #[allow(dead_code)]
+    pub fn shortest_press_ms(&self) -> Option<u64> {
+        self.shortest_press_ms
+    }
+
+    /// Returns longest completed press duration.
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Duration in milliseconds, None before first release
+    #[allow(dead_code)]
+    pub fn longest_press_ms(&self) -> Option<u64> {
+        self.longest_press_ms
+    }
+
+    /// Returns average completed press duration.
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Duration in milliseconds, None before first release
+    #[allow(dead_code)]
+    pub fn average_press_ms(&self) -> Option<u64> {
+        if self.completed_presses == 0 {
+            return None;
+        }
+        Some(self.total_press_ms / self.completed_presses as u64)
+    }
+
+    /// Returns number of bounces rejected by the debouncer.
+    ///
+    /// # Returns
+    /// * `u32` - Rejected bounce count
+    #[allow(dead_code)]
+    pub fn bounces_rejected(&self) -> u32 {
+        self.bounces_rejected
+    }
+
+    /// Returns time elapsed since the most recent press.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Elapsed milliseconds, None if never pressed
+    #[allow(dead_code)]
+    pub fn time_since_last_press_ms(&self, now_ms: u64) -> Option<u64> {
+        self.last_press_ms.map(|t| now_ms.saturating_sub(t))
+    }
+
+    /// Folds a completed press duration into the statistics.
+    ///
+    /// # Arguments
+    /// * `duration_ms` - Press duration in milliseconds
+    fn record_duration(&mut self, duration_ms: u64) {
+        self.completed_presses = self.completed_presses.saturating_add(1);
+        self.total_press_ms = self.total_press_ms.saturating_add(duration_ms);
+        if duration_ms >= LONG_PRESS_MS {
+            self.long_presses = self.long_presses.saturating_add(1);
+        }
+        let shortest = self.shortest_press_ms.unwrap_or(u64::MAX);
+        let longest = self.longest_press_ms.unwrap_or(0);
+        self.shortest_press_ms = Some(shortest.min(duration_ms));
+        self.longest_press_ms = Some(longest.max(duration_ms));
+    }
+}
+
+/// Button controller paired with fault monitoring and usage statistics.
+///
+/// # Details
+/// Wraps `ButtonController`, gates its state through `FaultMonitor`
+/// and feeds every reported event into `ButtonStats`.
+///
+/// # Fields
+/// * `controller` - Debounced button controller
+/// * `monitor` - Wiring fault monitor
+/// * `stats` - Accumulated statistics
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct TrackedButton {
+    controller: ButtonController,
+    monitor: FaultMonitor,
+    stats: ButtonStats,
+}
+
+impl TrackedButton {
+    /// Creates new tracked button.
+    ///
+    /// # Returns
+    /// * `Self` - New TrackedButton instance
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self::from_parts(ButtonController::new(), ButtonStats::new())
+    }
+
+    /// Creates tracked button from an existing controller and statistics.
+    ///
+    /// # Details
+    /// Uses default fault thresholds.
+    ///
+    /// # Arguments
+    /// * `controller` - Debounced button controller
+    /// * `stats` - Initial statistics
+    ///
+    /// # Returns
+    /// * `Self` - New TrackedButton instance
+    #[allow(dead_code)]
+    pub fn from_parts(controller: ButtonController, stats: ButtonStats) -> Self {
+        Self {
+            controller,
+            monitor: FaultMonitor::new(),
+            stats,
+        }
+    }
+
+    /// Updates button state and statistics with new GPIO sample.
+    ///
+    /// # Details
+    /// Events are suppressed while a wiring fault is active.
+    ///
+    /// # Arguments
+    /// * `gpio_high` - true if GPIO high (released), false if low (pressed)
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event if the debounced state changed
+    #[allow(dead_code)]
+    pub fn update(&mut self, gpio_high: bool, now_ms: u64) -> Option<ButtonEvent> {
+        let before = self.controller.bounces_rejected();
+        self.controller.update(gpio_high);
+        self.stats
+            .record_bounces(self.controller.bounces_rejected() - before);
+        let event = self
+            .monitor
+            .update(gpio_high, self.controller.is_pressed(), now_ms);
+        if let Some(event) = event {
+            self.stats.record(event, now_ms);
+        }
+        event
+    }
+
+    /// Resumes from sleep with the wake press already confirmed.
+    ///
+    /// # Details
+    /// Marks the button pressed without debouncing and reports the
+    /// press through the fault monitor and statistics.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current timestamp in milliseconds
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Pressed unless already pressed or faulted
+    #[allow(dead_code)]
+    pub fn wake_pressed(&mut self, now_ms: u64) -> Option<ButtonEvent> {
+        self.controller.resume_pressed();
+        let event = self.monitor.update(false, true, now_ms);
+        if let Some(event) = event {
+            self.stats.record(event, now_ms);
+        }
+        event
+    }
+
+    /// Returns the wrapped controller.
+    ///
+    /// # Returns
+    /// * `&ButtonController` - Debounced button controller
+    #[allow(dead_code)]
+    pub fn controller(&self) -> &ButtonController {
+        &self.controller
+    }
+
+    /// Returns the active wiring fault, if any.
+    ///
+    /// # Returns
+    /// * `Option<Fault>` - Active fault
+    #[allow(dead_code)]
+    pub fn fault(&self) -> Option<Fault> {
+        self.monitor.fault()
+    }
+
+    /// Returns accumulated statistics.
+    ///
+    /// # Returns
+    /// * `&ButtonStats` - Usage statistics
+    #[allow(dead_code)]
+    pub fn stats(&self) -> &ButtonStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEBOUNCE_COUNT;
+
+    fn press(stats: &mut ButtonStats, start: u64, duration: u64) {
+        stats.record(ButtonEvent::Pressed, start);
+        stats.record(ButtonEvent::Released, start + duration);
+    }
+
+    // ==================== ButtonStats Construction Tests ====================
+
+    #[test]
+    fn test_new_stats_empty() {
+        let stats = ButtonStats::new();
+        assert_eq!(stats.total_presses(), 0);
+        assert_eq!(stats.long_presses(), 0);
+        assert_eq!(stats.bounces_rejected(), 0);
+        assert_eq!(stats.shortest_press_ms(), None);
+        assert_eq!(stats.longest_press_ms(), None);
+        assert_eq!(stats.average_press_ms(), None);
+        assert_eq!(stats.time_since_last_press_ms(100), None);
+    }
+
+    #[test]
+    fn test_default_equals_new() {
+        assert_eq!(ButtonStats::default(), ButtonStats::new());
+    }
+
+    #[test]
+    fn test_restore_counters() {
+        let stats = ButtonStats::restore(10, 3, 7);
+        assert_eq!(stats.total_presses(), 10);
+        assert_eq!(stats.long_presses(), 3);
+        assert_eq!(stats.bounces_rejected(), 7);
+        assert_eq!(stats.average_press_ms(), None);
+    }
+
+    // ==================== Press Duration Tests ====================
+
+    #[test]
+    fn test_single_press_duration() {
+        let mut stats = ButtonStats::new();
+        press(&mut stats, 100, 250);
+        assert_eq!(stats.total_presses(), 1);
+        assert_eq!(stats.shortest_press_ms(), Some(250));
+        assert_eq!(stats.longest_press_ms(), Some(250));
+        assert_eq!(stats.average_press_ms(), Some(250));
+    }
+
+    #[test]
+    fn test_min_max_average() {
+        let mut stats = ButtonStats::new();
+        press(&mut stats, 0, 100);
+        press(&mut stats, 1000, 300);
+        press(&mut stats, 2000, 200);
+        assert_eq!(stats.shortest_press_ms(), Some(100));
+        assert_eq!(stats.longest_press_ms(), Some(300));
+        assert_eq!(stats.average_press_ms(), Some(200));
+    }
+
+    #[test]
+    fn test_long_press_counted() {
+        let mut stats = ButtonStats::new();
+        press(&mut stats, 0, LONG_PRESS_MS - 1);
+        press(&mut stats, 5000, LONG_PRESS_MS);
+        assert_eq!(stats.total_presses(), 2);
+        assert_eq!(stats.long_presses(), 1);
+    }
+
+    #[test]
+    fn test_release_without_press_ignored() {
+        let mut stats = ButtonStats::new();
+        stats.record(ButtonEvent::Released, 50);
+        assert_eq!(stats.average_press_ms(), None);
+    }
+
+    #[test]
+    fn test_press_in_progress_not_averaged() {
+        let mut stats = ButtonStats::new();
+        stats.record(ButtonEvent::Pressed, 10);
+        assert_eq!(stats.total_presses(), 1);
+        assert_eq!(stats.average_press_ms(), None);
+    }
+
+    #[test]
+    fn test_time_since_last_press() {
+        let mut stats = ButtonStats::new();
+        press(&mut stats, 100, 50);
+        assert_eq!(stats.time_since_last_press_ms(600), Some(500));
+    }
+
+    // ==================== Saturation Tests ====================
+
+    #[test]
+    fn test_bounces_saturate() {
+        let mut stats = ButtonStats::new();
+        stats.record_bounces(u32::MAX);
+        stats.record_bounces(5);
+        assert_eq!(stats.bounces_rejected(), u32::MAX);
+    }
+
+    #[test]
+    fn test_duration_sum_saturates() {
+        let mut stats = ButtonStats::new();
+        press(&mut stats, 0, u64::MAX);
+        press(&mut stats, 0, u64::MAX);
+        assert_eq!(stats.longest_press_ms(), Some(u64::MAX));
+        assert_eq!(stats.average_press_ms(), Some(u64::MAX / 2));
+    }
+
+    #[test]
+    fn test_clock_going_backwards_saturates() {
+        let mut stats = ButtonStats::new();
+        stats.record(ButtonEvent::Pressed, 500);
+        stats.record(ButtonEvent::Released, 100);
+        assert_eq!(stats.shortest_press_ms(), Some(0));
+        assert_eq!(stats.time_since_last_press_ms(0), Some(0));
+    }
+
+    // ==================== TrackedButton Tests ====================
+
+    #[test]
+    fn test_tracked_button_counts_press() {
+        let mut button = TrackedButton::new();
+        let mut now = 0;
+        for _ in 0..=DEBOUNCE_COUNT {
+            button.update(false, now);
+            now += 5;
+        }
+        for _ in 0..=DEBOUNCE_COUNT {
+            button.update(true, now);
+            now += 5;
+        }
+        assert_eq!(button.stats().total_presses(), 1);
+        assert_eq!(
+            button.stats().average_press_ms(),
+            Some((DEBOUNCE_COUNT as u64 + 1) * 5)
+        );
+        assert!(!button.controller().is_pressed());
+    }
+
+    #[test]
+    fn test_tracked_button_suppresses_stuck_press() {
+        let mut button = TrackedButton::new();
+        let mut now = 0;
+        for _ in 0..=DEBOUNCE_COUNT {
+            button.update(false, now);
+            now += 5;
+        }
+        button.update(false, now + crate::config::MAX_HOLD_MS);
+        assert_eq!(button.fault(), Some(Fault::Stuck));
+        assert_eq!(button.stats().total_presses(), 1);
+    }
+
+    #[test]
+    fn test_wake_press_counted() {
+        let mut button = TrackedButton::new();
+        assert_eq!(button.wake_pressed(100), Some(ButtonEvent::Pressed));
+        assert!(button.controller().is_pressed());
+        assert_eq!(button.stats().total_presses(), 1);
+        let mut now = 105;
+        let mut released = None;
+        for _ in 0..=DEBOUNCE_COUNT {
+            released = released.or(button.update(true, now));
+            now += 5;
+        }
+        assert_eq!(released, Some(ButtonEvent::Released));
+        assert_eq!(
+            button.stats().average_press_ms(),
+            Some((DEBOUNCE_COUNT as u64 + 1) * 5)
+        );
+    }
+
+    #[test]
+    fn test_tracked_button_counts_bounces() {
+        let mut button = TrackedButton::new();
+        for t in 0..4 {
+            button.update(t % 2 == 1, t);
+        }
+        assert_eq!(button.stats().bounces_rejected(), 2);
+        assert_eq!(button.stats().total_presses(), 0);
+    }
+}
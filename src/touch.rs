@@ -0,0 +1,391 @@
+/*
+ * @file touch.rs
+ * @brief Capacitive touch pad input with baseline tracking
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: touch.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Capacitive Touch Button.
+//!
+//! BRIEF:
+//! Turns charge-time measurements of a touch pad into a raw touched
+//! signal using a tracked baseline, drift compensation and threshold
+//! hysteresis, then debounces it with `ButtonController` so touch pads
+//! produce the same events as mechanical buttons.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::button::{ButtonController, ButtonEvent};
+use crate::config::{
+    DEBOUNCE_COUNT, TOUCH_BASELINE_SHIFT, TOUCH_CALIBRATION_SAMPLES, TOUCH_HYSTERESIS,
+    TOUCH_THRESHOLD,
+};
+
+/// Largest baseline filter shift.
+///
+/// # Details
+/// Larger shifts are clamped so the scaled baseline of any `u32`
+/// count fits the 64-bit accumulator.
+#[allow(dead_code)]
+pub const MAX_BASELINE_SHIFT: u8 = 16;
+
+/// Touch sensing configuration.
+///
+/// # Fields
+/// * `threshold` - Counts above baseline that register a touch
+/// * `hysteresis` - Counts below threshold required to release
+/// * `baseline_shift` - Baseline filter rate as a power of two, at most
+///   `MAX_BASELINE_SHIFT`
+/// * `calibration_samples` - Samples averaged for the initial baseline
+/// * `debounce_count` - Stable samples required for an event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct TouchConfig {
+    pub threshold: u32,
+    pub hysteresis: u32,
+    pub baseline_shift: u8,
+    pub calibration_samples: u32,
+    pub debounce_count: u32,
+}
+
+impl Default for TouchConfig {
+    /// Returns configuration from `config.rs`.
+    ///
+    /// # Returns
+    /// * `Self` - Default touch configuration
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self {
+            threshold: TOUCH_THRESHOLD,
+            hysteresis: TOUCH_HYSTERESIS,
+            baseline_shift: TOUCH_BASELINE_SHIFT,
+            calibration_samples: TOUCH_CALIBRATION_SAMPLES,
+            debounce_count: DEBOUNCE_COUNT,
+        }
+    }
+}
+
+/// Capacitive touch button.
+///
+/// # Details
+/// Samples are charge-time counts from any measurement method, such
+/// as timing a GPIO rising through its pull-up or a PIO counter; a
+/// finger adds capacitance and raises the count. The baseline starts
+/// as the average of the calibration samples and then follows the
+/// signal through an exponential filter while the pad is untouched,
+/// so a touch is never absorbed into the baseline.
+///
+/// # Fields
+/// * `config` - Touch configuration
+/// * `controller` - Debouncer fed with the raw touched signal
+/// * `baseline_acc` - Baseline scaled by `2^baseline_shift`
+/// * `calibration_sum` - Sum of calibration samples so far
+/// * `calibration_left` - Calibration samples still required
+/// * `touched` - Raw touched state after hysteresis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct TouchSensor {
+    config: TouchConfig,
+    controller: ButtonController,
+    baseline_acc: u64,
+    calibration_sum: u32,
+    calibration_left: u32,
+    touched: bool,
+}
+
+impl TouchSensor {
+    /// Creates touch sensor that calibrates on its first samples.
+    ///
+    /// # Details
+    /// A baseline shift above `MAX_BASELINE_SHIFT` is clamped.
+    ///
+    /// # Arguments
+    /// * `config` - Touch configuration
+    ///
+    /// # Returns
+    /// * `Self` - New TouchSensor instance
+    #[allow(dead_code)]
+    pub fn new(mut config: TouchConfig) -> Self {
+        config.baseline_shift = config.baseline_shift.min(MAX_BASELINE_SHIFT);
+        Self {
+            config,
+            controller: ButtonController::with_debounce_count(config.debounce_count),
+            baseline_acc: 0,
+            calibration_sum: 0,
+            calibration_left: config.calibration_samples.max(1),
+            touched: false,
+        }
+    }
+
+    /// Updates the sensor with a new charge-time measurement.
+    ///
+    /// # Arguments
+    /// * `count` - Raw charge-time count
+    ///
+    /// # Returns
+    /// * `Option<ButtonEvent>` - Event if the debounced state changed
+    #[allow(dead_code)]
+    pub fn update(&mut self, count: u32) -> Option<ButtonEvent> {
+        if self.calibration_left > 0 {
+            self.calibration_sum = self.calibration_sum.saturating_add(count);
+            self.calibration_left -= 1;
+            if self.calibration_left == 0 {
+                let samples = self.config.calibration_samples.max(1);
+                self.baseline_acc =
+                    ((self.calibration_sum / samples) as u64) << self.config.baseline_shift;
+                debug!("touch: baseline {}", self.baseline());
+            }
+            return None;
+        }
+        let delta = count.saturating_sub(self.baseline());
+        self.touched = if self.touched {
+            delta.saturating_add(self.config.hysteresis) >= self.config.threshold
+        } else {
+            delta >= self.config.threshold
+        };
+        if !self.touched {
+            self.baseline_acc = self
+                .baseline_acc
+                .saturating_sub(self.baseline() as u64)
+                .saturating_add(count as u64);
+        }
+        self.controller.update(!self.touched)
+    }
+
+    /// Restarts baseline calibration.
+    ///
+    /// # Details
+    /// Call with the pad untouched, for example after a long stuck touch.
+    #[allow(dead_code)]
+    pub fn recalibrate(&mut self) {
+        self.calibration_sum = 0;
+        self.calibration_left = self.config.calibration_samples.max(1);
+        self.touched = false;
+    }
+
+    /// Returns true once the initial baseline is known.
+    ///
+    /// # Returns
+    /// * `bool` - true if calibrated
+    #[allow(dead_code)]
+    pub fn is_calibrated(&self) -> bool {
+        self.calibration_left == 0
+    }
+
+    /// Returns the current baseline.
+    ///
+    /// # Returns
+    /// * `u32` - Baseline charge-time count
+    #[allow(dead_code)]
+    pub fn baseline(&self) -> u32 {
+        (self.baseline_acc >> self.config.baseline_shift).min(u32::MAX as u64) as u32
+    }
+
+    /// Returns the touch configuration in use.
+    ///
+    /// # Returns
+    /// * `&TouchConfig` - Configuration after clamping
+    #[allow(dead_code)]
+    pub fn config(&self) -> &TouchConfig {
+        &self.config
+    }
+
+    /// Returns the debouncer fed by the touch signal.
+    ///
+    /// # Returns
+    /// * `&ButtonController` - Debounced touch state
+    #[allow(dead_code)]
+    pub fn controller(&self) -> &ButtonController {
+        &self.controller
+    }
+}
+
+impl Default for TouchSensor {
+    /// Returns touch sensor with default configuration.
+    ///
+    /// # Returns
+    /// * `Self` - New TouchSensor with default values
+    #[allow(dead_code)]
+    fn default() -> Self {
+        Self::new(TouchConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibrated(baseline: u32) -> TouchSensor {
+        let mut sensor = TouchSensor::new(TouchConfig {
+            threshold: 50,
+            hysteresis: 15,
+            baseline_shift: 4,
+            calibration_samples: 4,
+            debounce_count: 2,
+        });
+        for _ in 0..4 {
+            sensor.update(baseline);
+        }
+        sensor
+    }
+
+    fn feed(sensor: &mut TouchSensor, count: u32, n: usize) -> Option<ButtonEvent> {
+        let mut last = None;
+        for _ in 0..n {
+            last = sensor.update(count).or(last);
+        }
+        last
+    }
+
+    // ==================== Calibration Tests ====================
+
+    #[test]
+    fn test_calibration_averages_samples() {
+        let mut sensor = TouchSensor::new(TouchConfig {
+            calibration_samples: 4,
+            ..TouchConfig::default()
+        });
+        for count in [100, 110, 90, 100] {
+            assert!(!sensor.is_calibrated());
+            assert_eq!(sensor.update(count), None);
+        }
+        assert!(sensor.is_calibrated());
+        assert_eq!(sensor.baseline(), 100);
+    }
+
+    #[test]
+    fn test_no_touch_during_calibration() {
+        let mut sensor = TouchSensor::default();
+        assert_eq!(feed(&mut sensor, 5000, 10), None);
+        assert!(!sensor.controller().is_pressed());
+    }
+
+    #[test]
+    fn test_recalibrate_resets_baseline() {
+        let mut sensor = calibrated(100);
+        sensor.recalibrate();
+        feed(&mut sensor, 300, 4);
+        assert_eq!(sensor.baseline(), 300);
+    }
+
+    // ==================== Touch Detection Tests ====================
+
+    #[test]
+    fn test_touch_pressed_after_debounce() {
+        let mut sensor = calibrated(100);
+        assert_eq!(feed(&mut sensor, 160, 2), None);
+        assert_eq!(feed(&mut sensor, 160, 1), Some(ButtonEvent::Pressed));
+    }
+
+    #[test]
+    fn test_below_threshold_ignored() {
+        let mut sensor = calibrated(100);
+        assert_eq!(feed(&mut sensor, 145, 20), None);
+    }
+
+    #[test]
+    fn test_hysteresis_holds_touch() {
+        let mut sensor = calibrated(100);
+        feed(&mut sensor, 170, 3);
+        assert_eq!(feed(&mut sensor, 140, 10), None);
+        assert!(sensor.controller().is_pressed());
+        assert_eq!(feed(&mut sensor, 130, 3), Some(ButtonEvent::Released));
+    }
+
+    #[test]
+    fn test_single_noise_spike_debounced() {
+        let mut sensor = calibrated(100);
+        for _ in 0..5 {
+            sensor.update(200);
+            sensor.update(100);
+        }
+        assert!(!sensor.controller().is_pressed());
+    }
+
+    // ==================== Range Tests ====================
+
+    #[test]
+    fn test_large_baseline_shift_clamped() {
+        let mut sensor = TouchSensor::new(TouchConfig {
+            baseline_shift: 40,
+            calibration_samples: 1,
+            ..TouchConfig::default()
+        });
+        assert_eq!(sensor.config().baseline_shift, MAX_BASELINE_SHIFT);
+        sensor.update(1000);
+        assert_eq!(sensor.baseline(), 1000);
+        feed(&mut sensor, 1010, 50);
+        assert!(sensor.baseline() >= 1000);
+    }
+
+    #[test]
+    fn test_full_range_counts_do_not_overflow() {
+        let mut sensor = TouchSensor::new(TouchConfig {
+            threshold: u32::MAX,
+            hysteresis: u32::MAX,
+            baseline_shift: MAX_BASELINE_SHIFT,
+            calibration_samples: 1,
+            debounce_count: 1,
+        });
+        sensor.update(u32::MAX);
+        assert_eq!(sensor.baseline(), u32::MAX);
+        feed(&mut sensor, u32::MAX, 100);
+        feed(&mut sensor, 0, 100);
+        assert!(sensor.baseline() < u32::MAX);
+    }
+
+    // ==================== Drift Compensation Tests ====================
+
+    #[test]
+    fn test_slow_drift_tracked_without_touch() {
+        let mut sensor = calibrated(100);
+        for count in 100..300 {
+            assert_eq!(sensor.update(count), None);
+        }
+        assert!(sensor.baseline() > 250);
+    }
+
+    #[test]
+    fn test_baseline_frozen_while_touched() {
+        let mut sensor = calibrated(100);
+        feed(&mut sensor, 200, 100);
+        assert_eq!(sensor.baseline(), 100);
+        assert!(sensor.controller().is_pressed());
+    }
+
+    #[test]
+    fn test_downward_drift_tracked() {
+        let mut sensor = calibrated(200);
+        feed(&mut sensor, 150, 200);
+        assert_eq!(sensor.baseline(), 150);
+    }
+}
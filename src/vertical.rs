@@ -0,0 +1,304 @@
+/*
+ * @file vertical.rs
+ * @brief Bit-parallel vertical counter debouncer for 32 inputs
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: vertical.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Vertical Counter Debouncer.
+//!
+//! BRIEF:
+//! Debounces up to 32 inputs at once from sample words, such as those
+//! pushed by a PIO state machine sampling a GPIO bank. Each lane
+//! follows exactly the `ButtonController` algorithm, which remains the
+//! reference model, and only changed lanes are reported.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use crate::config::MAX_DEBOUNCE_COUNT;
+
+/// Bit planes of each lane counter.
+///
+/// # Details
+/// Seven planes count to 127, covering `MAX_DEBOUNCE_COUNT`.
+#[allow(dead_code)]
+pub const COUNTER_PLANES: usize = 7;
+
+/// Largest supported debounce threshold.
+#[allow(dead_code)]
+pub const MAX_VERTICAL_THRESHOLD: u32 = (1 << COUNTER_PLANES) - 1;
+
+const _: () = assert!(
+    MAX_VERTICAL_THRESHOLD >= MAX_DEBOUNCE_COUNT,
+    "COUNTER_PLANES cannot count to MAX_DEBOUNCE_COUNT"
+);
+
+/// 32-lane vertical counter debouncer.
+///
+/// # Details
+/// Lane counters are stored bit-sliced: plane `n` holds bit `n` of
+/// every lane's counter, so one update costs a few dozen word
+/// operations regardless of how many lanes change. A lane restarts its
+/// count when its sample differs from the previous sample, counts up to
+/// the threshold while stable, and flips its debounced level once the
+/// threshold is reached. Levels are raw GPIO levels (1 = high).
+///
+/// # Fields
+/// * `state` - Debounced level of each lane
+/// * `raw` - Previous sample
+/// * `planes` - Bit-sliced lane counters
+/// * `threshold` - Stable samples required for a change
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub struct VerticalDebouncer {
+    state: u32,
+    raw: u32,
+    planes: [u32; COUNTER_PLANES],
+    threshold: u32,
+}
+
+impl VerticalDebouncer {
+    /// Creates debouncer with all lanes settled at `initial`.
+    ///
+    /// # Details
+    /// The threshold is clamped to 1..=`MAX_VERTICAL_THRESHOLD`.
+    ///
+    /// # Arguments
+    /// * `threshold` - Stable samples required for a change
+    /// * `initial` - Initial level of each lane
+    ///
+    /// # Returns
+    /// * `Self` - New VerticalDebouncer instance
+    #[allow(dead_code)]
+    pub fn new(threshold: u32, initial: u32) -> Self {
+        let threshold = threshold.clamp(1, MAX_VERTICAL_THRESHOLD);
+        let mut planes = [0; COUNTER_PLANES];
+        for (bit, plane) in planes.iter_mut().enumerate() {
+            if threshold & (1 << bit) != 0 {
+                *plane = u32::MAX;
+            }
+        }
+        Self {
+            state: initial,
+            raw: initial,
+            planes,
+            threshold,
+        }
+    }
+
+    /// Updates all lanes with a new sample word.
+    ///
+    /// # Arguments
+    /// * `sample` - Raw level of each lane
+    ///
+    /// # Returns
+    /// * `u32` - Lanes whose debounced level changed
+    #[allow(dead_code)]
+    pub fn update(&mut self, sample: u32) -> u32 {
+        let changed = sample ^ self.raw;
+        self.raw = sample;
+        let mut carry = !changed & !self.at_threshold();
+        for plane in self.planes.iter_mut() {
+            let next = (*plane ^ carry) & !changed;
+            carry &= *plane;
+            *plane = next;
+        }
+        let edges = self.at_threshold() & (self.raw ^ self.state);
+        self.state ^= edges;
+        edges
+    }
+
+    /// Returns debounced level of every lane.
+    ///
+    /// # Returns
+    /// * `u32` - Debounced levels (1 = high)
+    #[allow(dead_code)]
+    pub fn state(&self) -> u32 {
+        self.state
+    }
+
+    /// Returns the debounce threshold after clamping.
+    ///
+    /// # Returns
+    /// * `u32` - Stable samples required for a change
+    #[allow(dead_code)]
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns lanes whose counter equals the threshold.
+    ///
+    /// # Returns
+    /// * `u32` - Saturated lanes
+    fn at_threshold(&self) -> u32 {
+        self.planes
+            .iter()
+            .enumerate()
+            .fold(u32::MAX, |mask, (bit, &plane)| {
+                if self.threshold & (1 << bit) != 0 {
+                    mask & plane
+                } else {
+                    mask & !plane
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::{ButtonController, ButtonEvent};
+    use crate::config::DEBOUNCE_COUNT;
+
+    /// Deterministic pseudo-random sample source.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u32 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (self.0 >> 32) as u32
+        }
+    }
+
+    /// Runs both algorithms on the same trace and checks every step.
+    fn assert_equivalent(threshold: u32, samples: &[u32]) {
+        let mut vertical = VerticalDebouncer::new(threshold, u32::MAX);
+        let mut reference = [ButtonController::with_debounce_count(threshold); 32];
+        for &sample in samples {
+            let edges = vertical.update(sample);
+            for (lane, ctrl) in reference.iter_mut().enumerate() {
+                let high = sample & (1 << lane) != 0;
+                let event = ctrl.update(high);
+                assert_eq!(event.is_some(), edges & (1 << lane) != 0);
+                assert_eq!(ctrl.is_pressed(), vertical.state() & (1 << lane) == 0);
+                if let Some(event) = event {
+                    assert_eq!(event == ButtonEvent::Released, high);
+                }
+            }
+        }
+    }
+
+    /// Builds a bouncy trace where each lane holds levels for random runs.
+    fn bouncy_trace(seed: u64, len: usize, max_run: u32) -> Vec<u32> {
+        let mut rng = Lcg(seed);
+        let mut runs = [0u32; 32];
+        let mut level = u32::MAX;
+        (0..len)
+            .map(|_| {
+                for (lane, run) in runs.iter_mut().enumerate() {
+                    if *run == 0 {
+                        level ^= 1 << lane;
+                        *run = rng.next() % max_run + 1;
+                    }
+                    *run -= 1;
+                }
+                level
+            })
+            .collect()
+    }
+
+    // ==================== Construction Tests ====================
+
+    #[test]
+    fn test_threshold_clamped() {
+        assert_eq!(VerticalDebouncer::new(0, 0).threshold(), 1);
+        assert_eq!(
+            VerticalDebouncer::new(1000, 0).threshold(),
+            MAX_VERTICAL_THRESHOLD
+        );
+    }
+
+    #[test]
+    fn test_stable_input_no_edges() {
+        let mut deb = VerticalDebouncer::new(DEBOUNCE_COUNT, 0x0F0F_0F0F);
+        for _ in 0..20 {
+            assert_eq!(deb.update(0x0F0F_0F0F), 0);
+        }
+        assert_eq!(deb.state(), 0x0F0F_0F0F);
+    }
+
+    // ==================== Debounce Tests ====================
+
+    #[test]
+    fn test_lane_changes_after_threshold() {
+        let mut deb = VerticalDebouncer::new(3, u32::MAX);
+        for _ in 0..3 {
+            assert_eq!(deb.update(!0b100), 0);
+        }
+        assert_eq!(deb.update(!0b100), 0b100);
+        assert_eq!(deb.state(), !0b100);
+    }
+
+    #[test]
+    fn test_lanes_independent() {
+        let mut deb = VerticalDebouncer::new(2, u32::MAX);
+        deb.update(!0b01);
+        deb.update(!0b11);
+        assert_eq!(deb.update(!0b11), 0b01);
+        assert_eq!(deb.update(!0b11), 0b10);
+    }
+
+    #[test]
+    fn test_glitch_rejected() {
+        let mut deb = VerticalDebouncer::new(DEBOUNCE_COUNT, u32::MAX);
+        for _ in 0..10 {
+            assert_eq!(deb.update(0), 0);
+            assert_eq!(deb.update(u32::MAX), 0);
+        }
+        assert_eq!(deb.state(), u32::MAX);
+    }
+
+    // ==================== Equivalence Tests ====================
+
+    #[test]
+    fn test_equivalent_on_random_samples() {
+        let mut rng = Lcg(1);
+        let samples: Vec<u32> = (0..2000).map(|_| rng.next()).collect();
+        assert_equivalent(1, &samples);
+        assert_equivalent(DEBOUNCE_COUNT, &samples);
+    }
+
+    #[test]
+    fn test_equivalent_on_bouncy_traces() {
+        for threshold in [1, 2, DEBOUNCE_COUNT, 9] {
+            assert_equivalent(threshold, &bouncy_trace(threshold as u64, 3000, 12));
+        }
+    }
+
+    #[test]
+    fn test_equivalent_at_max_threshold() {
+        let trace = bouncy_trace(7, 4000, 300);
+        assert_equivalent(MAX_DEBOUNCE_COUNT, &trace);
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * @file watchdog.rs
+ * @brief Task liveness tracking and reset cause reporting
+ * @author Kevin Thomas
+ * @date 2025
+ *
+ * MIT License
+ *
+ * Copyright (c) 2025 Kevin Thomas
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! FILE: watchdog.rs
+//!
+//! DESCRIPTION:
+//! RP2350 Watchdog Liveness and Reset Cause.
+//!
+//! BRIEF:
+//! Collects per-task liveness reports so the watchdog is fed only when
+//! every task is making progress. Classifies the reset cause at boot
+//! and maps it to an LED indication pattern.
+//!
+//! AUTHOR: Kevin Thomas
+//! CREATION DATE: October 18, 2026
+//! UPDATE DATE: October 18, 2026
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::config::FAULT_BLINK_MS;
+use crate::led::{LedPattern, LedState};
+
+/// Liveness bit reported by the button task.
+#[allow(dead_code)]
+pub const BUTTON_TASK: u32 = 1 << 0;
+
+/// Liveness bit reported by the LED task.
+#[allow(dead_code)]
+pub const LED_TASK: u32 = 1 << 1;
+
+/// Task liveness tracker.
+///
+/// # Details
+/// Tasks set their bit on every loop iteration. The watchdog owner
+/// calls `check_and_clear` once per feed interval and feeds only when
+/// all required bits were set since the previous check. A task that
+/// deliberately blocks, such as while sleeping, parks its bit.
+///
+/// # Fields
+/// * `flags` - Bits reported since the last check
+/// * `parked` - Bits of tasks waiting on purpose
+/// * `required` - Bits that must be reported before feeding
+#[allow(dead_code)]
+pub struct Liveness {
+    flags: AtomicU32,
+    parked: AtomicU32,
+    required: u32,
+}
+
+impl Liveness {
+    /// Creates liveness tracker.
+    ///
+    /// # Arguments
+    /// * `required` - Bit mask of tasks that must report
+    ///
+    /// # Returns
+    /// * `Self` - New Liveness instance
+    #[allow(dead_code)]
+    pub const fn new(required: u32) -> Self {
+        Self {
+            flags: AtomicU32::new(0),
+            parked: AtomicU32::new(0),
+            required,
+        }
+    }
+
+    /// Reports that a task made progress.
+    ///
+    /// # Arguments
+    /// * `task` - Task liveness bit
+    #[allow(dead_code)]
+    pub fn report(&self, task: u32) {
+        self.flags.fetch_or(task, Ordering::Relaxed);
+    }
+
+    /// Marks a task as intentionally blocked.
+    ///
+    /// # Details
+    /// Parked tasks count as alive until unparked.
+    ///
+    /// # Arguments
+    /// * `task` - Task liveness bit
+    #[allow(dead_code)]
+    pub fn park(&self, task: u32) {
+        self.parked.fetch_or(task, Ordering::Relaxed);
+    }
+
+    /// Clears a task's parked state and reports it alive.
+    ///
+    /// # Arguments
+    /// * `task` - Task liveness bit
+    #[allow(dead_code)]
+    pub fn unpark(&self, task: u32) {
+        self.parked.fetch_and(!task, Ordering::Relaxed);
+        self.report(task);
+    }
+
+    /// Returns true if all required tasks reported, then clears reports.
+    ///
+    /// # Returns
+    /// * `bool` - true if the watchdog may be fed
+    #[allow(dead_code)]
+    pub fn check_and_clear(&self) -> bool {
+        let flags = self.flags.swap(0, Ordering::Relaxed) | self.parked.load(Ordering::Relaxed);
+        flags & self.required == self.required
+    }
+}
+
+/// Cause of the most recent reset.
+///
+/// # Variants
+/// * `PowerOn` - Power-on, brown-out or external reset
+/// * `WatchdogTimeout` - Watchdog expired without being fed
+/// * `WatchdogForced` - Software-triggered watchdog reset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(dead_code)]
+pub enum ResetCause {
+    PowerOn,
+    WatchdogTimeout,
+    WatchdogForced,
+}
+
+impl ResetCause {
+    /// Returns the LED pattern shown at boot for this reset cause.
+    ///
+    /// # Details
+    /// Watchdog timeouts blink three pulses; forced resets blink one.
+    /// Power-on resets show nothing.
+    ///
+    /// # Returns
+    /// * `LedPattern` - Boot indication pattern
+    #[allow(dead_code)]
+    pub fn led_pattern(&self) -> LedPattern {
+        let count = match self {
+            ResetCause::PowerOn => return LedPattern::Solid(LedState::Off),
+            ResetCause::WatchdogTimeout => 3,
+            ResetCause::WatchdogForced => 1,
+        };
+        LedPattern::Burst {
+            count,
+            on_ms: FAULT_BLINK_MS,
+            off_ms: FAULT_BLINK_MS,
+            pause_ms: 4 * FAULT_BLINK_MS,
+        }
+    }
+
+    /// Returns true if the reset was caused by the watchdog.
+    ///
+    /// # Returns
+    /// * `bool` - true for watchdog resets
+    #[allow(dead_code)]
+    pub fn is_watchdog(&self) -> bool {
+        !matches!(self, ResetCause::PowerOn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ==================== Liveness Tests ====================
+
+    #[test]
+    fn test_no_reports_not_alive() {
+        let live = Liveness::new(BUTTON_TASK | LED_TASK);
+        assert!(!live.check_and_clear());
+    }
+
+    #[test]
+    fn test_all_reports_alive() {
+        let live = Liveness::new(BUTTON_TASK | LED_TASK);
+        live.report(BUTTON_TASK);
+        live.report(LED_TASK);
+        assert!(live.check_and_clear());
+    }
+
+    #[test]
+    fn test_partial_reports_not_alive() {
+        let live = Liveness::new(BUTTON_TASK | LED_TASK);
+        live.report(BUTTON_TASK);
+        assert!(!live.check_and_clear());
+    }
+
+    #[test]
+    fn test_check_clears_reports() {
+        let live = Liveness::new(BUTTON_TASK | LED_TASK);
+        live.report(BUTTON_TASK);
+        live.report(LED_TASK);
+        assert!(live.check_and_clear());
+        assert!(!live.check_and_clear());
+    }
+
+    #[test]
+    fn test_repeated_reports_idempotent() {
+        let live = Liveness::new(BUTTON_TASK);
+        for _ in 0..5 {
+            live.report(BUTTON_TASK);
+        }
+        assert!(live.check_and_clear());
+    }
+
+    #[test]
+    fn test_parked_task_counts_alive() {
+        let live = Liveness::new(BUTTON_TASK | LED_TASK);
+        live.park(LED_TASK);
+        live.report(BUTTON_TASK);
+        assert!(live.check_and_clear());
+        live.report(BUTTON_TASK);
+        assert!(live.check_and_clear());
+    }
+
+    #[test]
+    fn test_unparked_task_must_report() {
+        let live = Liveness::new(BUTTON_TASK | LED_TASK);
+        live.park(LED_TASK);
+        live.unpark(LED_TASK);
+        live.report(BUTTON_TASK);
+        assert!(live.check_and_clear());
+        live.report(BUTTON_TASK);
+        assert!(!live.check_and_clear());
+    }
+
+    #[test]
+    fn test_task_bits_distinct() {
+        assert_eq!(BUTTON_TASK & LED_TASK, 0);
+    }
+
+    // ==================== ResetCause Tests ====================
+
+    #[test]
+    fn test_power_on_pattern_off() {
+        let pattern = ResetCause::PowerOn.led_pattern();
+        assert_eq!(pattern.state_at(0), LedState::Off);
+        assert!(!ResetCause::PowerOn.is_watchdog());
+    }
+
+    #[test]
+    fn test_watchdog_patterns_distinct() {
+        let timeout = ResetCause::WatchdogTimeout.led_pattern();
+        let forced = ResetCause::WatchdogForced.led_pattern();
+        assert_ne!(timeout, forced);
+        assert_eq!(timeout.state_at(0), LedState::On);
+        assert!(ResetCause::WatchdogTimeout.is_watchdog());
+        assert!(ResetCause::WatchdogForced.is_watchdog());
+    }
+}